@@ -1,6 +1,6 @@
 use clap::{App, Arg, Shell, SubCommand};
 
-use crate::vote::VoteStrategy;
+use relaty::vote::VoteStrategy;
 
 pub(crate) fn build_cli() -> App<'static, 'static> {
     App::new("relaty")
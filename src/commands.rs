@@ -6,7 +6,7 @@ use std::{
 use clap::Values;
 use regex::Regex;
 
-use crate::{error::Error, rel_vec::RelVec};
+use relaty::{error::Error, rel_vec::RelVec};
 
 pub(crate) fn new(output: &str) -> Result<(), Error> {
     let rv = RelVec::new();
@@ -49,7 +49,7 @@ pub(crate) fn print_screen(
         if nameonly {
             println!("{}", e.name);
         } else {
-            println!("{}", e.to_string());
+            println!("{}", e);
         }
     }
 
@@ -84,7 +84,7 @@ pub(crate) fn print_file(
         if nameonly {
             writer.write_all(e.name.as_bytes())?;
         } else {
-            writer.write_all(e.to_string().as_bytes())?;
+            write!(writer, "{}", e)?;
         }
         writer.write_all(b"\n")?;
     }
@@ -107,7 +107,7 @@ pub(crate) fn remove(input: &str, output: &str, filter: &str) -> Result<(), Erro
     let re = Regex::new(filter)?;
 
     for i in rv.iter().filter(|i| re.is_match(&i.name)) {
-        println!("{}", i.to_string());
+        println!("{}", i);
     }
 
     println!("\x1b[31mDo you want to remove these entries? [y/N]\x1b[0m");
@@ -116,18 +116,12 @@ pub(crate) fn remove(input: &str, output: &str, filter: &str) -> Result<(), Erro
     let _s = reader.read_line(&mut ans)?;
 
     match ans.chars().next() {
-        Some(c) => match c {
-            'y' | 'Y' => {
-                rv.remove(|i| re.is_match(&i.name));
+        Some('y' | 'Y') => {
+            rv.remove(|i| re.is_match(&i.name));
 
-                rv.save(output)
-            }
-            _ => {
-                println!("Nothing resetted");
-                rv.save(output)
-            }
-        },
-        None => {
+            rv.save(output)
+        }
+        _ => {
             println!("Nothing resetted");
             rv.save(output)
         }
@@ -139,7 +133,7 @@ pub(crate) fn reset(input: &str, output: &str, filter: &str) -> Result<(), Error
     let re = Regex::new(filter)?;
 
     for i in rv.iter().filter(|i| re.is_match(&i.name)) {
-        println!("{}", i.to_string());
+        println!("{}", i);
     }
 
     println!("\x1b[31mDo you want to reset these entries? [y/N]\x1b[0m");
@@ -148,20 +142,12 @@ pub(crate) fn reset(input: &str, output: &str, filter: &str) -> Result<(), Error
     let _s = reader.read_line(&mut ans)?;
 
     match ans.chars().next() {
-        Some(c) => match c {
-            'y' | 'Y' => {
-                rv.iter_mut()
-                    .filter(|i| re.is_match(&i.name))
-                    .for_each(|i| i.reset());
+        Some('y' | 'Y') => {
+            rv.reset_with(|i| re.is_match(&i.name));
 
-                rv.save(output)
-            }
-            _ => {
-                println!("Nothing resetted");
-                rv.save(output)
-            }
-        },
-        None => {
+            rv.save(output)
+        }
+        _ => {
             println!("Nothing resetted");
             rv.save(output)
         }
@@ -173,7 +159,7 @@ pub(crate) fn lock(input: &str, output: &str, filter: &str, lock: bool) -> Resul
     let re = Regex::new(filter)?;
 
     for i in rv.iter().filter(|i| re.is_match(&i.name)) {
-        println!("{}", i.to_string());
+        println!("{}", i);
     }
 
     println!(
@@ -185,20 +171,14 @@ pub(crate) fn lock(input: &str, output: &str, filter: &str, lock: bool) -> Resul
     let _s = reader.read_line(&mut ans)?;
 
     match ans.chars().next() {
-        Some(c) => match c {
-            'y' | 'Y' => {
-                rv.iter_mut()
-                    .filter(|i| re.is_match(&i.name))
-                    .for_each(|i| i.locked = lock);
+        Some('y' | 'Y') => {
+            rv.iter_mut()
+                .filter(|i| re.is_match(&i.name))
+                .for_each(|i| i.locked = lock);
 
-                rv.save(output)
-            }
-            _ => {
-                println!("Nothing {}locked", if lock { "" } else { "un" });
-                rv.save(output)
-            }
-        },
-        None => {
+            rv.save(output)
+        }
+        _ => {
             println!("Nothing {}locked", if lock { "" } else { "un" });
             rv.save(output)
         }
@@ -236,26 +216,12 @@ pub(crate) fn stats(input: &str) -> Result<(), Error> {
     if let Some(max_p) = max_p {
         println!("Maximum percentage: \x1b[34m{}\x1b[0m", max_p);
     }
-    for i in &[0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0] {
-        let c = rv
-            .iter()
-            .filter(|e| e.percentage() >= *i && e.percentage() < i + 10.0)
-            .count();
-        println!(
-            "\x1b[33m[{:0>3},{:0>3})\x1b[0m: \x1b[34m{}\x1b[0m \x1b[31m{}\x1b[0m",
-            i,
-            i + 10.0,
-            "|".repeat(c / 5),
-            c
-        );
-    }
-    {
-        let c = rv
-            .iter()
-            .filter(|e| e.percentage() >= 90.0 && e.percentage() <= 100.0)
-            .count();
+    for (i, c) in rv.histogram(10).into_iter().enumerate() {
         println!(
-            "\x1b[33m[090,100]\x1b[0m: \x1b[34m{}\x1b[0m \x1b[31m{}\x1b[0m",
+            "\x1b[33m[{:0>3},{:0>3}{}\x1b[0m: \x1b[34m{}\x1b[0m \x1b[31m{}\x1b[0m",
+            i * 10,
+            i * 10 + 10,
+            if i == 9 { "]" } else { ")" },
             "|".repeat(c / 5),
             c
         );
@@ -1,11 +1,26 @@
-use std::{io, num::ParseIntError};
+use std::{fmt, io, num::ParseIntError};
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
     IoError(io::Error),
     Serde(serde_json::Error),
+    #[cfg(feature = "bincode")]
+    Bincode(bincode::Error),
     Regex(regex::Error),
+    Toml(String),
     Parse(ParseIntError),
+    InvalidEntry(String),
+    Tsv(usize, String),
+    Overflow(String),
+    NotFound(String),
+    DuplicateName(String),
+    SameEntry(String),
+    ZeroMargin,
+    InvalidListName(String),
+    NotInMatchup(usize),
+    ChecksumMismatch,
+    UnsupportedVersion(u32),
     ArgError,
 }
 
@@ -21,6 +36,25 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "bincode")]
+impl From<bincode::Error> for Error {
+    fn from(e: bincode::Error) -> Self {
+        Error::Bincode(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Error::Toml(e.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for Error {
+    fn from(e: toml::ser::Error) -> Self {
+        Error::Toml(e.to_string())
+    }
+}
+
 impl From<regex::Error> for Error {
     fn from(e: regex::Error) -> Self {
         Error::Regex(e)
@@ -33,14 +67,30 @@ impl From<ParseIntError> for Error {
     }
 }
 
-impl ToString for Error {
-    fn to_string(&self) -> String {
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::IoError(e) => format!("IOError: {}", e.to_string()),
-            Error::Serde(e) => format!("Serialization Error: {}", e.to_string()),
-            Error::Regex(e) => format!("RegEx Error: {}", e.to_string()),
-            Error::Parse(e) => format!("Parse Error: {}", e.to_string()),
-            Error::ArgError => "argument is no UTF-8 string".to_string(),
+            Error::IoError(e) => write!(f, "IOError: {}", e),
+            Error::Serde(e) => write!(f, "Serialization Error: {}", e),
+            #[cfg(feature = "bincode")]
+            Error::Bincode(e) => write!(f, "Bincode Error: {}", e),
+            Error::Regex(e) => write!(f, "RegEx Error: {}", e),
+            Error::Toml(e) => write!(f, "TOML Error: {}", e),
+            Error::Parse(e) => write!(f, "Parse Error: {}", e),
+            Error::InvalidEntry(name) => {
+                write!(f, "Invalid Entry: {} has more wins than votes", name)
+            }
+            Error::Tsv(line, msg) => write!(f, "TSV Error in line {}: {}", line, msg),
+            Error::Overflow(name) => write!(f, "Overflow: counters of {} exceed u32", name),
+            Error::NotFound(name) => write!(f, "Entry {} not found", name),
+            Error::DuplicateName(name) => write!(f, "Entry {} already exists", name),
+            Error::SameEntry(name) => write!(f, "Entry {} can't be compared to itself", name),
+            Error::ZeroMargin => write!(f, "Margin of a vote must be at least 1"),
+            Error::InvalidListName(name) => write!(f, "{:?} is no valid list name", name),
+            Error::NotInMatchup(i) => write!(f, "Entry {} is not part of the matchup", i),
+            Error::ChecksumMismatch => write!(f, "Checksum mismatch: file is damaged"),
+            Error::UnsupportedVersion(v) => write!(f, "Unsupported file format version {}", v),
+            Error::ArgError => write!(f, "argument is no UTF-8 string"),
         }
     }
 }
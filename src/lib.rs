@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate serde_derive;
+
+pub mod error;
+pub mod rel_vec;
+mod rng;
+pub mod session;
+pub mod vote;
+pub mod workspace;
+
+pub use crate::error::Error;
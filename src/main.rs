@@ -1,19 +1,13 @@
-#[macro_use]
-extern crate serde_derive;
-
 mod cli;
 mod commands;
-mod error;
-mod rel_vec;
-mod vote;
 
 use std::{convert::TryInto, io, str::FromStr};
 
 use crate::commands::{add, create, new, remove, reset, stats};
-use crate::error::Error;
-use crate::vote::{vote, VoteStrategy};
 use clap::Shell;
 use commands::{from, lock, print_file, print_screen};
+use relaty::error::Error;
+use relaty::vote::{vote, VoteStrategy};
 
 fn main() -> Result<(), Error> {
     let matches = crate::cli::build_cli().get_matches();
@@ -114,7 +108,7 @@ fn main() -> Result<(), Error> {
             .try_into()?;
         let info = matches.is_present("info");
 
-        println!("Using strategy \"{}\"", strategy.to_string());
+        println!("Using strategy \"{}\"", strategy);
         return vote(input, output, rounds, strategy.choose_function(), info);
     }
 
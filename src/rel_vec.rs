@@ -1,17 +1,27 @@
 use crate::error::Error;
+use crate::rng::{RelRng, RngState};
+use crate::session::{BestOfSession, Session};
+use crate::vote::VoteStrategy;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression, Crc};
+use rand::prelude::SliceRandom;
 use rand::Rng;
-use rand::{prelude::SliceRandom, rngs::ThreadRng};
-use std::cmp::Ordering;
-use std::fs::File;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Write as _};
+use std::fs::{self, File};
 use std::{
     io,
     ops::{Index, IndexMut},
 };
 use std::{
-    io::{BufRead, BufReader, BufWriter},
+    io::{BufRead, BufReader, BufWriter, Write},
     ops::Deref,
 };
-use std::{ops::DerefMut, path::Path};
+use std::{
+    ops::DerefMut,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RelEntry {
@@ -23,6 +33,33 @@ pub struct RelEntry {
     pub votes: u32,
     #[serde(rename = "l", default)]
     pub locked: bool,
+    /// Excluded from pairing for now, but unlike `locked` not considered settled.
+    #[serde(rename = "d", default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// Head-to-head record: how often this entry won against the named opponent.
+    #[serde(rename = "h", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub beaten: BTreeMap<String, u32>,
+    /// Relative chance of being picked by `random_pair`. 0 excludes the entry from every
+    /// pair strategy, negative and non-finite weights count as 0.
+    #[serde(
+        rename = "g",
+        default = "default_weight",
+        skip_serializing_if = "is_default_weight"
+    )]
+    pub weight: f64,
+    #[serde(rename = "t", default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form data like notes or URLs. It is stored, but never used for ranking.
+    #[serde(rename = "m", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+fn is_default_weight(weight: &f64) -> bool {
+    *weight == default_weight()
 }
 
 impl RelEntry {
@@ -32,6 +69,11 @@ impl RelEntry {
             wins,
             votes,
             locked: false,
+            disabled: false,
+            beaten: BTreeMap::new(),
+            weight: default_weight(),
+            tags: Vec::new(),
+            meta: BTreeMap::new(),
         }
     }
 
@@ -39,37 +81,197 @@ impl RelEntry {
         self.wins = 0;
         self.votes = 0;
         self.locked = false;
+        self.beaten.clear();
+    }
+
+    /// Adds the stats and head-to-head record of `other`, locking if either is locked.
+    /// Metadata of `other` is only taken for keys missing here.
+    ///
+    /// If any counter would overflow, `Error::Overflow` is returned and nothing is changed.
+    fn absorb(&mut self, other: RelEntry) -> Result<(), Error> {
+        let wins = self.wins.checked_add(other.wins);
+        let votes = self.votes.checked_add(other.votes);
+        let beaten_fits = other
+            .beaten
+            .iter()
+            .all(|(name, &c)| self.wins_against(name).checked_add(c).is_some());
+
+        let (wins, votes) = match (wins, votes) {
+            (Some(wins), Some(votes)) if beaten_fits => (wins, votes),
+            _ => return Err(Error::Overflow(self.name.clone())),
+        };
+
+        self.wins = wins;
+        self.votes = votes;
+        self.locked |= other.locked;
+        for (name, c) in other.beaten {
+            *self.beaten.entry(name).or_insert(0) += c;
+        }
+        for (key, value) in other.meta {
+            self.meta.entry(key).or_insert(value);
+        }
+
+        Ok(())
+    }
+
+    /// The weight used for pairing, with negative and non-finite weights as 0.
+    fn pair_weight(&self) -> f64 {
+        if self.weight.is_finite() && self.weight > 0.0 {
+            self.weight
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether the entry is offered for pairing: neither locked nor disabled and with a
+    /// positive weight.
+    fn is_pairable(&self) -> bool {
+        !self.locked && !self.disabled && self.pair_weight() > 0.0
+    }
+
+    pub fn wins_against(&self, other: &str) -> u32 {
+        self.beaten.get(other).copied().unwrap_or(0)
+    }
+
+    pub fn shares_tag(&self, other: &RelEntry) -> bool {
+        self.tags.iter().any(|t| other.tags.contains(t))
+    }
+
+    /// Like `==`, but also compares wins, votes and the locked state.
+    pub fn deep_eq(&self, other: &RelEntry) -> bool {
+        self.name == other.name
+            && self.wins == other.wins
+            && self.votes == other.votes
+            && self.locked == other.locked
+    }
+
+    /// Sets the metadata `key` to `value`, returning the previous value.
+    pub fn set_meta(&mut self, key: String, value: String) -> Option<String> {
+        self.meta.insert(key, value)
+    }
+
+    pub fn get_meta(&self, key: &str) -> Option<&str> {
+        self.meta.get(key).map(String::as_str)
+    }
+
+    /// Formats like `to_string`, but with a custom separator and marker for locked entries.
+    pub fn format_with(&self, sep: &str, locked_marker: &str) -> String {
+        let mut s = String::new();
+
+        self.write_with(&mut s, sep, locked_marker)
+            .expect("writing to a String does not fail");
+        s
+    }
+
+    fn write_with<W: fmt::Write>(&self, w: &mut W, sep: &str, locked_marker: &str) -> fmt::Result {
+        write!(
+            w,
+            "{}{}{}/{}{}{}{}",
+            self.name,
+            sep,
+            self.wins,
+            self.votes,
+            sep,
+            self.display_percentage(),
+            if self.locked { locked_marker } else { "" }
+        )
+    }
+
+    /// Percentage for display with one decimal, e.g. "33.3%", or "—" without votes instead
+    /// of "NaN%". Used by `to_string` and `format_with`.
+    pub fn display_percentage(&self) -> String {
+        if self.votes == 0 {
+            "—".to_string()
+        } else {
+            format!("{:.1}%", self.percentage_clamped())
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.wins <= self.votes
     }
 
     pub fn percentage(&self) -> f64 {
         f64::from(self.wins) * 100.0 / f64::from(self.votes)
     }
 
+    /// Number of votes the stats of this entry are based on, for displaying sample sizes.
+    ///
+    /// This counts votes, not comparisons: a `RelVec::vote_weighted` win with a margin of 3
+    /// (or a strong `RelVec::vote_strength` win) adds 3. For now this equals `votes`. Code
+    /// showing sample sizes should use this instead of `votes`, so it keeps working if
+    /// partial results like draws are counted differently later.
+    pub fn effective_votes(&self) -> u32 {
+        self.votes
+    }
+
+    /// `percentage` limited to 0 to 100, so invalid entries with more wins than votes are
+    /// never shown above 100%. Still NaN without votes. Used by `display_percentage` and
+    /// `RelVec::to_svg`.
+    pub fn percentage_clamped(&self) -> f64 {
+        self.percentage().clamp(0.0, 100.0)
+    }
+
+    /// Percentage with Laplace smoothing, `(wins + alpha) / (votes + 2 * alpha) * 100`.
+    ///
+    /// Adding `alpha` wins and losses pulls entries with few votes towards 50%. For `alpha > 0`
+    /// this is never NaN, entries without votes get exactly 50%.
+    pub fn smoothed_percentage(&self, alpha: f64) -> f64 {
+        (f64::from(self.wins) + alpha) * 100.0 / (f64::from(self.votes) + 2.0 * alpha)
+    }
+
+    /// Standard error `sqrt(p * (1 - p) / n)` of the win proportion `p` (between 0 and 1).
+    pub fn std_error(&self) -> Option<f64> {
+        if self.votes == 0 {
+            return None;
+        }
+
+        let n = f64::from(self.votes);
+        let p = f64::from(self.wins) / n;
+
+        Some((p * (1.0 - p) / n).sqrt())
+    }
+
+    /// Lower bound `p - z * std_error` of the normal approximation interval of the win
+    /// proportion.
+    pub fn lower_ci(&self, z: f64) -> Option<f64> {
+        self.std_error()
+            .map(|se| f64::from(self.wins) / f64::from(self.votes) - z * se)
+    }
+
     pub fn compare_percentage(&self, other: &RelEntry) -> Ordering {
         // TODO NaN should be handled different
-        let ap = self.wins * other.votes;
-        let bp = other.wins * self.votes;
+        let ap = u64::from(self.wins) * u64::from(other.votes);
+        let bp = u64::from(other.wins) * u64::from(self.votes);
 
         ap.cmp(&bp)
     }
+
+    /// Like `compare_percentage`, but breaks ties by the number of votes, so of 1/2 and 50/100
+    /// the better established 50/100 is greater.
+    pub fn compare_percentage_then_votes(&self, other: &RelEntry) -> Ordering {
+        self.compare_percentage(other)
+            .then_with(|| self.votes.cmp(&other.votes))
+    }
 }
 
+impl Default for RelEntry {
+    fn default() -> Self {
+        Self::new(String::new(), 0, 0)
+    }
+}
+
+/// Entries are equal if their **names** are equal, wins, votes and everything else are
+/// ignored. Use `RelEntry::deep_eq` to compare the stats as well.
 impl PartialEq for RelEntry {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
     }
 }
 
-impl ToString for RelEntry {
-    fn to_string(&self) -> String {
-        format!(
-            "{} - {}/{} - {}%{}",
-            self.name,
-            self.wins,
-            self.votes,
-            self.percentage(),
-            if self.locked { " [L]" } else { "" }
-        )
+impl fmt::Display for RelEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_with(f, " - ", " [L]")
     }
 }
 
@@ -82,739 +284,6514 @@ impl From<String> for RelEntry {
 #[derive(Clone, Debug)]
 pub struct RelVec {
     pub inner: Vec<RelEntry>,
-    rng: ThreadRng,
+    rng: RelRng,
+    /// Maximum number of comparisons in this session, `None` means unlimited.
+    pub session_limit: Option<usize>,
+    comparisons_done: usize,
+    autosave: Option<(PathBuf, usize)>,
+    on_vote: VoteCallback,
+    /// Position of every name, see `build_index`.
+    name_index: Option<HashMap<String, usize>>,
+    skips: usize,
+    /// Recently skipped pairs of names with the round (`comparisons_done + skips`) they
+    /// were skipped in.
+    skipped: Vec<(String, String, usize)>,
+    /// Every vote in order, `None` while the log is turned off, see `enable_vote_log`.
+    vote_log: Option<Vec<VoteEntry>>,
 }
 
-impl RelVec {
-    pub fn new() -> Self {
-        Self {
-            inner: Vec::new(),
-            rng: rand::thread_rng(),
-        }
+/// Number of rounds (votes or skips) a skipped pair is avoided for.
+const SKIP_COOLDOWN: usize = 10;
+/// How often `choose_pair` asks again if it got a recently skipped pair.
+const SKIP_RETRIES: usize = 3;
+
+/// Callback invoked with `(winner, loser)` after every vote.
+///
+/// It is owned by its `RelVec`. Cloning a `RelVec` does not clone the callback, the clone
+/// starts without one.
+#[derive(Default)]
+struct VoteCallback(Option<Box<dyn FnMut(usize, usize)>>);
+
+impl Clone for VoteCallback {
+    fn clone(&self) -> Self {
+        VoteCallback(None)
     }
+}
 
-    pub fn create(names: Vec<String>) -> Self {
-        Self {
-            inner: names.into_iter().map(|s| RelEntry::new(s, 0, 0)).collect(),
-            rng: rand::thread_rng(),
+impl fmt::Debug for VoteCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "VoteCallback(Some(..))"),
+            None => write!(f, "VoteCallback(None)"),
         }
     }
+}
 
-    pub fn from<P: AsRef<Path>>(file: P) -> Result<Self, io::Error> {
-        let f = File::open(file)?;
-        let reader = BufReader::new(f);
+/// Result of `RelVec::sync_names`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SyncSummary {
+    pub added: usize,
+    pub removed: usize,
+}
 
-        Ok(Self {
-            inner: reader
-                .lines()
-                .map(|r| r.map(|s| RelEntry::new(s, 0, 0)))
-                .collect::<Result<Vec<RelEntry>, io::Error>>()?,
-            rng: rand::thread_rng(),
-        })
-    }
+/// Differences between two lists as computed by `diff`, entries are matched by name.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<EntryChange>,
+}
 
-    pub fn load<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
-        let f = File::open(file)?;
-        let reader = BufReader::new(f);
+/// An entry whose wins, votes or locked state differ between two lists.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EntryChange {
+    pub name: String,
+    pub before: EntryStats,
+    pub after: EntryStats,
+}
 
-        Ok(Self {
-            inner: serde_json::from_reader(reader)?,
-            rng: rand::thread_rng(),
-        })
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EntryStats {
+    pub wins: u32,
+    pub votes: u32,
+    pub locked: bool,
+}
+
+impl From<&RelEntry> for EntryStats {
+    fn from(e: &RelEntry) -> Self {
+        EntryStats {
+            wins: e.wins,
+            votes: e.votes,
+            locked: e.locked,
+        }
     }
+}
 
-    pub fn save<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
-        let f = File::create(file)?;
-        let writer = BufWriter::new(f);
+/// How `RelVec::merge_with` handles an entry whose name is in both lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Adds wins, votes and head-to-head records, locked if either is locked.
+    Sum,
+    /// Keeps the entry of the list merged into.
+    KeepLeft,
+    /// Replaces the entry with the one of the other list.
+    KeepRight,
+    /// Keeps the entry with more votes, the one of the list merged into on a tie.
+    Max,
+}
 
-        serde_json::to_writer(writer, &self.inner)?;
-        Ok(())
-    }
+/// Summary of a list as returned by `RelVec::stats`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelStats {
+    pub entries: usize,
+    pub unlocked: usize,
+    /// Sum of the votes of all entries, so every comparison is counted twice.
+    pub total_votes: u64,
+    /// Mean percentage of the entries with votes, `None` if there are none.
+    pub mean_percentage: Option<f64>,
+    /// Share (0 to 1) of the entries with at least one vote.
+    pub coverage: f64,
+}
 
-    pub fn add(&mut self, name: String) {
-        self.push(name.into());
-    }
+/// A single vote as recorded by the vote log, see `RelVec::enable_vote_log`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoteEntry {
+    pub winner: String,
+    pub loser: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
 
-    pub fn remove<F: FnMut(&RelEntry) -> bool>(&mut self, mut filter: F) {
-        self.retain(|i| !filter(i))
-    }
+/// A pair of entries to compare, as chosen by `RelVec::next_matchup`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Matchup {
+    pub left: usize,
+    pub right: usize,
+}
 
-    pub fn sort_percentage(&mut self) {
-        self.sort_by(|a: &RelEntry, b: &RelEntry| a.compare_percentage(b).reverse())
+impl Matchup {
+    pub fn left_entry<'a>(&self, rv: &'a RelVec) -> &'a RelEntry {
+        &rv[self.left]
     }
 
-    pub fn reduced(&self) -> Vec<usize> {
-        self.inner
-            .iter()
-            .enumerate()
-            .filter(|(_, e)| !e.locked)
-            .map(|(i, _)| i)
-            .collect()
+    pub fn right_entry<'a>(&self, rv: &'a RelVec) -> &'a RelEntry {
+        &rv[self.right]
     }
+}
 
-    pub fn min_votes(&mut self) -> Vec<usize> {
-        let mut min = u32::max_value();
-        let mut v = Vec::new();
-
-        for i in 0..self.len() {
-            if self[i].locked {
-                continue;
-            }
-            match self[i].votes.cmp(&min) {
-                Ordering::Less => {
-                    min = self[i].votes;
-                    v = Vec::new();
-                    v.push(i);
-                }
-                Ordering::Equal => {
-                    v.push(i);
-                }
-                Ordering::Greater => {}
-            }
-        }
-
-        v
+impl From<(usize, usize)> for Matchup {
+    fn from((left, right): (usize, usize)) -> Self {
+        Matchup { left, right }
     }
+}
 
-    pub fn random_pair(&mut self) -> Option<(usize, usize)> {
-        let reduced: Vec<usize> = self.reduced();
-
-        if reduced.len() < 2 {
-            return None;
-        }
-
-        let i1 = self.rng.gen_range(0..reduced.len());
-        let i2 = self.rng.gen_range(0..(reduced.len() - 1));
-        if i2 >= i1 {
-            Some((reduced[i1], reduced[i2 + 1]))
-        } else {
-            Some((reduced[i1], reduced[i2]))
-        }
-    }
+/// On-disk format: a bare array of entries, or an object that also holds the rng state.
+/// Both use the short keys of `RelEntry` (version 1). Version 2 is an object with a
+/// `version` field and entries with full field names, see `save_v2`.
+enum Stored {
+    Entries(Vec<RelEntry>),
+    Versioned(StoredV2),
+    WithRngState(StoredV1),
+}
 
-    pub fn min_pair(&mut self) -> Option<(usize, usize)> {
-        let reduced = self.reduced();
-        let mins = self.min_votes();
-        if reduced.len() < 2 {
-            return None;
+impl Stored {
+    /// Picks the format by the shape of `value` before parsing it, so an error in an entry
+    /// is reported as such instead of as a file matching none of the formats.
+    fn from_value(value: serde_json::Value) -> Result<Self, Error> {
+        if value.is_array() {
+            return Ok(Stored::Entries(serde_json::from_value(value)?));
         }
 
-        let i1 = mins[self.rng.gen_range(0..mins.len())];
-        let i2 = reduced[self.rng.gen_range(0..(reduced.len() - 1))];
-        if i2 >= i1 {
-            Some((i1, i2 + 1))
-        } else {
-            Some((i1, i2))
+        match value.get("version") {
+            Some(version) => match serde_json::from_value(version.clone())? {
+                2 => Ok(Stored::Versioned(serde_json::from_value(value)?)),
+                version => Err(Error::UnsupportedVersion(version)),
+            },
+            None => Ok(Stored::WithRngState(serde_json::from_value(value)?)),
         }
     }
+}
 
-    pub fn equal_pair(&mut self) -> Option<(usize, usize)> {
-        let mut reduced = self.reduced();
+#[derive(Deserialize)]
+struct StoredV1 {
+    entries: Vec<RelEntry>,
+    #[serde(default)]
+    rng_state: Option<RngState>,
+}
 
-        if reduced.len() < 2 {
-            return None;
-        }
+#[derive(Serialize, Deserialize)]
+struct StoredV2 {
+    version: u32,
+    entries: Vec<EntryV2>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rng_state: Option<RngState>,
+}
 
-        reduced.shuffle(&mut self.rng);
+/// `RelEntry` with full field names, used by version 2 of the file format.
+#[derive(Serialize, Deserialize)]
+struct EntryV2 {
+    name: String,
+    #[serde(default)]
+    wins: u32,
+    #[serde(default)]
+    votes: u32,
+    #[serde(default)]
+    locked: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    beaten: BTreeMap<String, u32>,
+    #[serde(default = "default_weight", skip_serializing_if = "is_default_weight")]
+    weight: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    meta: BTreeMap<String, String>,
+}
 
-        for i1 in 0..reduced.len() {
-            for i2 in i1 + 1..reduced.len() {
-                if (self[reduced[i2]].percentage() - self[reduced[i1]].percentage()).abs()
-                    < f64::EPSILON
-                {
-                    return Some((reduced[i1], reduced[i2]));
-                }
-            }
+impl From<RelEntry> for EntryV2 {
+    fn from(e: RelEntry) -> Self {
+        EntryV2 {
+            name: e.name,
+            wins: e.wins,
+            votes: e.votes,
+            locked: e.locked,
+            disabled: e.disabled,
+            beaten: e.beaten,
+            weight: e.weight,
+            tags: e.tags,
+            meta: e.meta,
         }
-        None
     }
+}
 
-    pub fn nearest_pair(&mut self) -> Option<(usize, usize)> {
-        let mut reduced = self.reduced();
-
-        if reduced.len() < 2 {
-            return None;
+impl From<EntryV2> for RelEntry {
+    fn from(e: EntryV2) -> Self {
+        RelEntry {
+            name: e.name,
+            wins: e.wins,
+            votes: e.votes,
+            locked: e.locked,
+            disabled: e.disabled,
+            beaten: e.beaten,
+            weight: e.weight,
+            tags: e.tags,
+            meta: e.meta,
         }
+    }
+}
 
-        reduced.shuffle(&mut self.rng);
+/// Binary file format of `save_bin`. Bincode is not self-describing, so unlike `RelEntry`
+/// every field is always written.
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+struct StoredBin {
+    entries: Vec<EntryBin>,
+    rng_state: Option<RngState>,
+}
 
-        let mut min = None;
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+struct EntryBin {
+    name: String,
+    wins: u32,
+    votes: u32,
+    locked: bool,
+    disabled: bool,
+    beaten: BTreeMap<String, u32>,
+    weight: f64,
+    tags: Vec<String>,
+    meta: BTreeMap<String, String>,
+}
 
-        for i1 in 0..reduced.len() {
-            for i2 in i1 + 1..reduced.len() {
-                let d2 = (self[reduced[i2]].percentage() - self[reduced[i1]].percentage()).abs();
-                match min {
-                    Some((_, _, d)) => {
-                        if d2 < d {
-                            min = Some((i1, i2, d2));
-                        }
-                    }
-                    None => {
-                        min = Some((i1, i2, d2));
-                    }
-                }
-            }
+#[cfg(feature = "bincode")]
+impl From<RelEntry> for EntryBin {
+    fn from(e: RelEntry) -> Self {
+        EntryBin {
+            name: e.name,
+            wins: e.wins,
+            votes: e.votes,
+            locked: e.locked,
+            disabled: e.disabled,
+            beaten: e.beaten,
+            weight: e.weight,
+            tags: e.tags,
+            meta: e.meta,
         }
-
-        min.map(|(a, b, _)| (reduced[a], reduced[b]))
     }
+}
 
-    pub fn min_equal_pair(&mut self) -> Option<(usize, usize)> {
-        if self.rng.gen_bool(0.5) {
-            match self.equal_pair() {
-                Some((a, b)) => Some((a, b)),
-                None => self.min_pair(),
-            }
-        } else {
-            self.min_pair()
+#[cfg(feature = "bincode")]
+impl From<EntryBin> for RelEntry {
+    fn from(e: EntryBin) -> Self {
+        RelEntry {
+            name: e.name,
+            wins: e.wins,
+            votes: e.votes,
+            locked: e.locked,
+            disabled: e.disabled,
+            beaten: e.beaten,
+            weight: e.weight,
+            tags: e.tags,
+            meta: e.meta,
         }
     }
 }
 
-impl PartialEq for RelVec {
-    fn eq(&self, other: &Self) -> bool {
-        self.inner == other.inner
-    }
+/// TOML has no top-level arrays, so entries are stored as an array of tables.
+#[derive(Serialize, Deserialize)]
+struct TomlFile {
+    #[serde(default)]
+    entries: Vec<RelEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rng_state: Option<RngState>,
 }
 
-impl Deref for RelVec {
-    type Target = Vec<RelEntry>;
+#[derive(Serialize)]
+#[serde(untagged)]
+enum StoredRef<'a> {
+    Entries(&'a [RelEntry]),
+    WithRngState {
+        entries: &'a [RelEntry],
+        rng_state: RngState,
+    },
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+impl<'a> StoredRef<'a> {
+    fn new(entries: &'a [RelEntry], rng_state: Option<RngState>) -> Self {
+        match rng_state {
+            Some(rng_state) => StoredRef::WithRngState { entries, rng_state },
+            None => StoredRef::Entries(entries),
+        }
     }
 }
 
-impl DerefMut for RelVec {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+impl RelVec {
+    pub fn new() -> Self {
+        Self {
+            inner: Vec::new(),
+            rng: RelRng::thread(),
+            session_limit: None,
+            comparisons_done: 0,
+            autosave: None,
+            on_vote: VoteCallback::default(),
+            name_index: None,
+            skips: 0,
+            skipped: Vec::new(),
+            vote_log: None,
+        }
     }
-}
 
-impl AsRef<Vec<RelEntry>> for RelVec {
-    fn as_ref(&self) -> &Vec<RelEntry> {
-        &self.inner
+    /// Uses a generator seeded with `seed` instead of the thread local one, making pair
+    /// selection reproducible.
+    ///
+    /// The seed and the position of the generator are written by `save` and restored by
+    /// `load`, so a resumed session continues the same sequence. This only works with a
+    /// seeded generator, the thread local one is never stored.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = RelRng::seeded(seed);
     }
-}
 
-impl AsMut<Vec<RelEntry>> for RelVec {
-    fn as_mut(&mut self) -> &mut Vec<RelEntry> {
-        &mut self.inner
+    fn from_stored(stored: Stored) -> Result<Self, Error> {
+        match stored {
+            Stored::Entries(inner) => Ok(Self {
+                inner,
+                ..Self::new()
+            }),
+            Stored::Versioned(StoredV2 {
+                entries, rng_state, ..
+            }) => Ok(Self {
+                inner: entries.into_iter().map(RelEntry::from).collect(),
+                rng: rng_state.map_or_else(RelRng::thread, RelRng::from_state),
+                ..Self::new()
+            }),
+            Stored::WithRngState(StoredV1 { entries, rng_state }) => Ok(Self {
+                inner: entries,
+                rng: rng_state.map_or_else(RelRng::thread, RelRng::from_state),
+                ..Self::new()
+            }),
+        }
     }
-}
 
-impl Index<usize> for RelVec {
-    type Output = RelEntry;
+    fn stored(&self) -> StoredRef<'_> {
+        StoredRef::new(&self.inner, self.rng.state())
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.inner[index]
+    fn with_rng<T, F: FnOnce(&Self, &mut RelRng) -> T>(&mut self, f: F) -> T {
+        let mut rng = self.rng.clone();
+        let t = f(self, &mut rng);
+
+        self.rng = rng;
+        t
     }
-}
 
-impl IndexMut<usize> for RelVec {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.inner[index]
+    pub fn create(names: Vec<String>) -> Self {
+        Self {
+            inner: names.into_iter().map(|s| RelEntry::new(s, 0, 0)).collect(),
+            ..Self::new()
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::{
-        cmp::Ordering,
-        fs::{self, File},
-        io::{BufWriter, Write},
-    };
+    pub fn from_strs(names: &[&str]) -> Self {
+        Self::create(names.iter().map(|s| s.to_string()).collect())
+    }
+
+    pub fn from<P: AsRef<Path>>(file: P) -> Result<Self, io::Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(f);
 
-    use super::{RelEntry, RelVec};
+        Ok(Self {
+            inner: reader
+                .lines()
+                .map(|r| r.map(|s| RelEntry::new(s, 0, 0)))
+                .collect::<Result<Vec<RelEntry>, io::Error>>()?,
+            ..Self::new()
+        })
+    }
 
-    #[test]
-    fn rel_entry_new() {
-        assert_eq!(
-            RelEntry {
-                name: "abc".to_owned(),
-                wins: 125132,
-                votes: 12551,
-                locked: false
+    /// Like `from`, but skips lines whose name was already seen, so only the first occurrence
+    /// becomes an entry. Also returns the number of skipped lines.
+    pub fn from_unique<P: AsRef<Path>>(file: P) -> Result<(Self, usize), io::Error> {
+        let all = Self::from(file)?;
+        let mut seen = HashSet::new();
+        let total = all.len();
+        let inner: Vec<RelEntry> = all
+            .inner
+            .into_iter()
+            .filter(|e| seen.insert(e.name.clone()))
+            .collect();
+        let skipped = total - inner.len();
+
+        Ok((
+            Self {
+                inner,
+                ..Self::new()
             },
-            RelEntry::new("abc".to_owned(), 125132, 12551)
-        );
+            skipped,
+        ))
     }
 
-    #[test]
-    fn rel_entry_reset() {
-        let a = RelEntry {
-            name: "abc".to_owned(),
-            wins: 0,
-            votes: 0,
-            locked: false,
-        };
-        let mut b = RelEntry {
-            name: "abc".to_owned(),
-            wins: 125132,
-            votes: 12551,
-            locked: true,
-        };
+    /// Reads every `.txt` file in `dir` like `from`, tagging each entry with the name of its
+    /// file without the extension. Files are read in order of their names, other files and
+    /// subdirectories are skipped.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        let mut files = Vec::new();
 
-        b.reset();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
 
-        assert_eq!(a, b);
+            if path.is_file() && path.extension().is_some_and(|e| e == "txt") {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut rv = Self::new();
+
+        for file in files {
+            let tag = file
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            for mut e in Self::from(&file)?.inner {
+                e.tags.push(tag.clone());
+                rv.push(e);
+            }
+        }
+
+        Ok(rv)
     }
 
-    #[test]
-    fn rel_entry_percentage() {
-        let mut e = RelEntry::new("abc".to_owned(), 0, 0);
+    /// Reads tab separated `name<TAB>wins<TAB>votes` lines, a line with only a name gets
+    /// 0/0. Empty lines are skipped.
+    pub fn from_tsv<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(f);
+        let mut rv = Self::new();
 
-        assert!(e.percentage().is_nan());
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
 
-        e.votes = 1;
+            if line.is_empty() {
+                continue;
+            }
 
-        assert_eq!(e.percentage(), 0.0);
+            let fields: Vec<&str> = line.split('\t').collect();
+            let entry = match fields.as_slice() {
+                [name] => RelEntry::new(name.to_string(), 0, 0),
+                [name, wins, votes] => {
+                    let parse = |field: &str| {
+                        field
+                            .trim()
+                            .parse::<u32>()
+                            .map_err(|e| Error::Tsv(i + 1, format!("{}: {:?}", e, field)))
+                    };
+
+                    RelEntry::new(name.to_string(), parse(wins)?, parse(votes)?)
+                }
+                _ => {
+                    return Err(Error::Tsv(
+                        i + 1,
+                        format!("expected 1 or 3 fields, found {}", fields.len()),
+                    ))
+                }
+            };
 
-        e.wins = 1;
+            rv.push(entry);
+        }
 
-        assert_eq!(e.percentage(), 100.0);
+        Ok(rv)
+    }
 
-        e.votes = 2;
+    /// Copies the entries with their stats, locks and head-to-head records reset.
+    pub fn names_only(&self) -> Self {
+        let mut rv = Self {
+            inner: self.inner.clone(),
+            ..Self::new()
+        };
 
-        assert_eq!(e.percentage(), 50.0);
+        rv.iter_mut().for_each(|e| e.reset());
+        rv
+    }
 
-        e.wins = 125;
-        e.votes = 312;
+    /// Splits into the entries matching `f` and the rest, keeping stats and locks.
+    /// Both parts start with a fresh rng.
+    pub fn partition<F: Fn(&RelEntry) -> bool>(self, f: F) -> (RelVec, RelVec) {
+        let (matching, rest) = self.inner.into_iter().partition(|e| f(e));
 
-        assert_eq!(e.percentage(), 40.06410256410256);
+        (
+            Self {
+                inner: matching,
+                ..Self::new()
+            },
+            Self {
+                inner: rest,
+                ..Self::new()
+            },
+        )
     }
 
-    #[test]
-    fn rel_entry_compare_percentage() {
-        let mut a = RelEntry::new("abc".to_owned(), 0, 0);
-        let mut b = RelEntry::new("abc".to_owned(), 0, 0);
+    pub fn into_names(self) -> Vec<String> {
+        self.inner.into_iter().map(|e| e.name).collect()
+    }
 
-        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+    /// Consumes the list and returns its entries sorted like `sort_percentage`, without
+    /// cloning them.
+    pub fn into_sorted_percentage(mut self) -> Vec<RelEntry> {
+        self.sort_percentage();
+        self.inner
+    }
 
-        b.votes = 1;
+    pub fn load<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(f);
 
-        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+        Self::from_stored(Stored::from_value(serde_json::from_reader(reader)?)?)
+    }
 
-        a.wins = 1;
-        a.votes = 2;
+    /// Like `load`, but sorts the entries with `sort_percentage` right away.
+    pub fn load_then_sort_percentage<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let mut rv = Self::load(file)?;
 
-        assert_eq!(a.compare_percentage(&b), Ordering::Greater);
+        rv.sort_percentage();
+        Ok(rv)
+    }
 
-        b.wins = 1;
+    /// Like `load`, but fails if an entry has more wins than votes.
+    pub fn load_validated<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let rv = Self::load(file)?;
 
-        assert_eq!(a.compare_percentage(&b), Ordering::Less);
+        rv.validate()?;
+        Ok(rv)
+    }
 
-        b.votes = 2;
+    /// Loads a plain JSON array of names (`["a","b"]`) as fresh entries.
+    pub fn load_names_json<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(f);
+        let names: Vec<String> = serde_json::from_reader(reader)?;
 
-        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+        Ok(Self::create(names))
     }
 
-    #[test]
-    fn rel_entry_partial_eq() {
-        let a = RelEntry {
-            name: "abc".to_owned(),
-            wins: 125132,
-            votes: 1263,
-            locked: false,
-        };
-        let b = RelEntry {
-            name: "abc".to_owned(),
-            wins: 1251,
-            votes: 1361621,
-            locked: false,
-        };
+    pub fn save<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let writer = BufWriter::new(f);
 
-        assert_eq!(a, b);
+        serde_json::to_writer(writer, &self.stored())?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_entry_to_string() {
-        let a = RelEntry {
-            name: "abc".to_owned(),
-            wins: 12,
-            votes: 36,
-            locked: false,
-        };
+    /// Like `save`, but writes only the entries with votes, by descending percentage, and
+    /// without the rng state. The list itself is not changed.
+    pub fn save_ranked_only<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let writer = BufWriter::new(f);
+        let mut ranked: Vec<&RelEntry> = self.iter().filter(|e| e.votes > 0).collect();
 
-        assert_eq!(a.to_string(), "abc - 12/36 - 33.333333333333336%");
+        ranked.sort_by(|a, b| b.compare_percentage(a));
+        serde_json::to_writer(writer, &ranked)?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_entry_from_string() {
-        let a = RelEntry {
-            name: "abc".to_owned(),
-            wins: 0,
-            votes: 0,
-            locked: false,
+    /// Saves in version 2 of the format: indented JSON with full field names (`name`, `wins`,
+    /// `votes`, `locked`, ...) instead of the short keys, which is easier to edit by hand.
+    /// `load` detects the version on its own.
+    pub fn save_v2<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let writer = BufWriter::new(f);
+        let stored = StoredV2 {
+            version: 2,
+            entries: self.inner.iter().cloned().map(EntryV2::from).collect(),
+            rng_state: self.rng.state(),
         };
-        let b = "abc".to_owned().into();
 
-        assert_eq!(a, b);
+        serde_json::to_writer_pretty(writer, &stored)?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_vec_new() {
-        let a = RelVec {
-            inner: Vec::new(),
-            rng: rand::thread_rng(),
-        };
-        let b = RelVec::new();
+    /// Like `save`, but writes indented JSON which is easier to read, edit and diff.
+    pub fn save_pretty<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let writer = BufWriter::new(f);
 
-        assert_eq!(a, b);
+        serde_json::to_writer_pretty(writer, &self.stored())?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_vec_create() {
-        let a = RelVec {
-            inner: Vec::from([
-                RelEntry::new("abc".to_string(), 1251, 16162),
-                RelEntry::new("adsga".to_string(), 1251, 1236),
-            ]),
-            rng: rand::thread_rng(),
+    /// Like `load`, but verifies the checksum written by `save_checked` and fails with
+    /// `Error::ChecksumMismatch` if it is missing or does not match.
+    pub fn load_checked<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let content = fs::read(file)?;
+        let (json, sum) = match content.iter().rposition(|&b| b == b'\n') {
+            Some(i) => (&content[..i], &content[i + 1..]),
+            None => return Err(Error::ChecksumMismatch),
         };
-        let b = RelVec::create(Vec::from(["abc".to_string(), "adsga".to_string()]));
 
-        assert_eq!(a, b);
+        if sum != format!("{:08x}", checksum(json)).as_bytes() {
+            return Err(Error::ChecksumMismatch);
+        }
+
+        Self::from_stored(Stored::from_value(serde_json::from_slice(json)?)?)
     }
 
-    #[test]
-    fn rel_vec_from() {
-        let file = File::create("_rel_vec_from.txt").unwrap();
-        let mut writer = BufWriter::new(file);
+    /// Like `save`, but appends a line with the CRC-32 of the JSON, see `load_checked`.
+    pub fn save_checked<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let mut content = serde_json::to_vec(&self.stored())?;
+        let sum = checksum(&content);
 
-        writer.write_all(b"a\nb\nc\n").unwrap();
+        content.extend_from_slice(format!("\n{:08x}", sum).as_bytes());
+        fs::write(file, content)?;
+        Ok(())
+    }
 
-        drop(writer);
+    /// Like `load`, but reads the file with `tokio::fs` and parses it on the blocking thread
+    /// pool, so it does not stall the async runtime. Needs the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub async fn load_async<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let content = tokio::fs::read(file).await?;
+        let value = tokio::task::spawn_blocking(move || serde_json::from_slice(&content))
+            .await
+            .map_err(io::Error::from)??;
 
-        let a = RelVec::create(["a".to_string(), "b".to_string(), "c".to_string()].to_vec());
-        let b = RelVec::from("_rel_vec_from.txt").unwrap();
+        Self::from_stored(Stored::from_value(value)?)
+    }
 
-        fs::remove_file("_rel_vec_from.txt").unwrap();
+    /// Like `save`, but serializes on the blocking thread pool and writes the file with
+    /// `tokio::fs`. Needs the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub async fn save_async<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let entries = self.inner.clone();
+        let rng_state = self.rng.state();
+        let content = tokio::task::spawn_blocking(move || {
+            serde_json::to_vec(&StoredRef::new(&entries, rng_state))
+        })
+        .await
+        .map_err(io::Error::from)??;
 
-        assert_eq!(a, b);
+        tokio::fs::write(file, content).await?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_vec_load() {
-        let file = File::create("_rel_vec_load.txt").unwrap();
-        let mut writer = BufWriter::new(file);
-
-        writer
-            .write_all(b"[{\"n\":\"abc\",\"w\":2,\"v\":3}]")
-            .unwrap();
+    /// Loads a binary file as written by `save_bin`. Needs the `bincode` feature.
+    #[cfg(feature = "bincode")]
+    pub fn load_bin<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let f = File::open(file)?;
+        let stored: StoredBin = bincode::deserialize_from(BufReader::new(f))?;
 
-        drop(writer);
+        Ok(Self {
+            inner: stored.entries.into_iter().map(RelEntry::from).collect(),
+            rng: stored
+                .rng_state
+                .map_or_else(RelRng::thread, RelRng::from_state),
+            ..Self::new()
+        })
+    }
 
-        let a = RelVec {
-            inner: [RelEntry::new("abc".to_owned(), 2, 3)].to_vec(),
-            rng: rand::thread_rng(),
+    /// Saves in a compact binary format with bincode, which is much faster to load than JSON
+    /// for large lists. Needs the `bincode` feature.
+    ///
+    /// The format is not human-readable and has no version: files can only be read as long
+    /// as the fields of `RelEntry` stay the same.
+    #[cfg(feature = "bincode")]
+    pub fn save_bin<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let mut writer = BufWriter::new(f);
+        let stored = StoredBin {
+            entries: self.inner.iter().cloned().map(EntryBin::from).collect(),
+            rng_state: self.rng.state(),
         };
-        let b = RelVec::load("_rel_vec_load.txt").unwrap();
-
-        fs::remove_file("_rel_vec_load.txt").unwrap();
 
-        assert_eq!(a, b);
+        bincode::serialize_into(&mut writer, &stored)?;
+        writer.flush()?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_vec_save() {
-        let rv = RelVec::create(["abc".to_string()].to_vec());
-        rv.save("_rel_vec_save.txt").unwrap();
+    /// Like `load`, but reads a gzip-compressed file as written by `save_gz`.
+    pub fn load_gz<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(GzDecoder::new(f));
 
-        let a = b"[{\"n\":\"abc\",\"w\":0,\"v\":0,\"l\":false}]";
-        let b = fs::read("_rel_vec_save.txt").unwrap();
+        Self::from_stored(Stored::from_value(serde_json::from_reader(reader)?)?)
+    }
 
-        fs::remove_file("_rel_vec_save.txt").unwrap();
+    /// Like `save`, but gzip-compresses the JSON.
+    pub fn save_gz<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let mut encoder = GzEncoder::new(BufWriter::new(f), Compression::default());
 
-        assert_eq!(a, b.as_slice());
+        serde_json::to_writer(&mut encoder, &self.stored())?;
+        encoder.finish()?;
+        Ok(())
     }
 
-    #[test]
-    fn rel_vec_add() {
-        let mut rv = RelVec {
-            inner: Vec::new(),
-            rng: rand::thread_rng(),
-        };
+    /// Loads a JSON Lines file as written by `save_jsonl`. Blank lines are skipped.
+    pub fn load_jsonl<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(f);
+        let mut inner = Vec::new();
 
-        rv.add("abc".to_owned());
+        for line in reader.lines() {
+            let line = line?;
 
-        assert_eq!(
-            rv,
-            RelVec {
-                inner: [RelEntry::new("abc".to_owned(), 0, 0)].to_vec(),
-                rng: rand::thread_rng()
+            if !line.trim().is_empty() {
+                inner.push(serde_json::from_str(&line)?);
             }
-        )
+        }
+
+        Ok(RelVec {
+            inner,
+            ..RelVec::new()
+        })
     }
 
-    #[test]
-    fn rel_vec_remove() {
-        let mut rv = RelVec {
-            inner: [RelEntry::new("abc".to_owned(), 0, 0)].to_vec(),
-            rng: rand::thread_rng(),
+    /// Saves as JSON Lines, one entry object per line. The rng state is not stored.
+    pub fn save_jsonl<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let mut writer = BufWriter::new(f);
+
+        for e in self.iter() {
+            serde_json::to_writer(&mut writer, e)?;
+            writeln!(writer)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Loads a TOML file as written by `save_toml`.
+    pub fn load_toml<P: AsRef<Path>>(file: P) -> Result<Self, Error> {
+        let content = fs::read_to_string(file)?;
+        let stored: TomlFile = toml::from_str(&content)?;
+
+        Self::from_stored(Stored::WithRngState(StoredV1 {
+            entries: stored.entries,
+            rng_state: stored.rng_state,
+        }))
+    }
+
+    /// Saves as TOML, one `[[entries]]` table per entry.
+    ///
+    /// Unlike JSON the keys of an entry are written in alphabetical order, and the
+    /// head-to-head record `h` comes last as a sub-table (`[entries.h]`), since TOML
+    /// requires plain values before tables.
+    pub fn save_toml<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let stored = TomlFile {
+            entries: self.inner.clone(),
+            rng_state: self.rng.state(),
         };
+        let value = toml::Value::try_from(&stored)?;
 
-        rv.remove(|entry| entry.name.len() == 3);
+        fs::write(file, toml::to_string(&value)?)?;
+        Ok(())
+    }
 
-        assert_eq!(rv, RelVec::new());
+    /// Like `==`, but compares the entries with `RelEntry::deep_eq`.
+    pub fn deep_eq(&self, other: &RelVec) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a.deep_eq(b))
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.iter().find(|e| !e.is_valid()) {
+            Some(e) => Err(Error::InvalidEntry(e.name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Resets stats, lock and head-to-head record (see `RelEntry::reset`) of every entry
+    /// matching `predicate`. The wins of the other entries against the reset ones are
+    /// removed from their head-to-head records as well.
+    pub fn reset_with<F: Fn(&RelEntry) -> bool>(&mut self, predicate: F) {
+        let reset: HashSet<String> = self
+            .iter()
+            .filter(|e| predicate(e))
+            .map(|e| e.name.clone())
+            .collect();
+
+        for e in self.iter_mut() {
+            if reset.contains(&e.name) {
+                e.reset();
+            } else {
+                e.beaten.retain(|name, _| !reset.contains(name));
+            }
+        }
+    }
+
+    pub fn reset_all(&mut self) {
+        self.reset_with(|_| true)
+    }
+
+    /// Resets the unlocked entries, locked ones keep their stats.
+    pub fn reset_unlocked(&mut self) {
+        self.reset_with(|e| !e.locked)
+    }
+
+    /// Builds an index from names to positions, making `position` and `contains` O(1).
+    ///
+    /// The methods of `RelVec` that add, remove, rename or reorder entries keep the index up
+    /// to date. Changing the entries directly, through `inner` or the `Vec` methods, does not
+    /// and needs another call to `build_index`.
+    pub fn build_index(&mut self) {
+        let mut index = HashMap::with_capacity(self.len());
+
+        for (i, e) in self.iter().enumerate() {
+            index.entry(e.name.clone()).or_insert(i);
+        }
+
+        self.name_index = Some(index);
+    }
+
+    pub fn drop_index(&mut self) {
+        self.name_index = None;
+    }
+
+    fn refresh_index(&mut self) {
+        if self.name_index.is_some() {
+            self.build_index();
+        }
+    }
+
+    /// Position of the first entry called `name`.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        match &self.name_index {
+            Some(index) => index.get(name).copied(),
+            None => self.iter().position(|e| e.name == name),
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    pub fn add(&mut self, name: String) {
+        if let Some(index) = &mut self.name_index {
+            index.entry(name.clone()).or_insert(self.inner.len());
+        }
+
+        self.push(name.into());
+    }
+
+    pub fn remove<F: FnMut(&RelEntry) -> bool>(&mut self, mut filter: F) {
+        self.retain(|i| !filter(i));
+        self.refresh_index();
+    }
+
+    /// Like `remove`, but returns the removed entries in their original order.
+    pub fn drain_filter<F: FnMut(&RelEntry) -> bool>(&mut self, mut filter: F) -> Vec<RelEntry> {
+        let (removed, kept) = self.inner.drain(..).partition(|e| filter(e));
+
+        self.inner = kept;
+        self.refresh_index();
+        removed
+    }
+
+    /// Renames the entry called `old` to `new`, keeping its stats.
+    ///
+    /// Fails if there is no such entry or if another entry is already called `new`.
+    /// Head-to-head records of the other entries are renamed as well.
+    pub fn rename(&mut self, old: &str, new: String) -> Result<(), Error> {
+        let index = match self.position(old) {
+            Some(index) => index,
+            None => return Err(Error::NotFound(old.to_string())),
+        };
+
+        if old == new {
+            return Ok(());
+        }
+        if self.contains(&new) {
+            return Err(Error::DuplicateName(new));
+        }
+
+        for e in self.iter_mut() {
+            if let Some(count) = e.beaten.remove(old) {
+                e.beaten.insert(new.clone(), count);
+            }
+        }
+        for (x, y, _) in &mut self.skipped {
+            if x == old || y == old {
+                let other = if x == old { y.clone() } else { x.clone() };
+                let (a, b) = skip_key(&new, &other);
+                *x = a.to_string();
+                *y = b.to_string();
+            }
+        }
+
+        if let Some(names) = &mut self.name_index {
+            names.remove(old);
+            names.insert(new.clone(), index);
+        }
+
+        self[index].name = new;
+        Ok(())
+    }
+
+    /// Excludes the entry called `name` from pairing, keeping its stats and lock.
+    pub fn disable(&mut self, name: &str) -> Result<(), Error> {
+        self.set_disabled(name, true)
+    }
+
+    /// Makes the entry called `name` available for pairing again, see `disable`.
+    pub fn enable(&mut self, name: &str) -> Result<(), Error> {
+        self.set_disabled(name, false)
+    }
+
+    fn set_disabled(&mut self, name: &str, disabled: bool) -> Result<(), Error> {
+        match self.position(name) {
+            Some(i) => {
+                self[i].disabled = disabled;
+                Ok(())
+            }
+            None => Err(Error::NotFound(name.to_string())),
+        }
+    }
+
+    /// Returns the entry at `index`, or `None` if out of range instead of panicking like
+    /// `rv[index]`.
+    pub fn get(&self, index: usize) -> Option<&RelEntry> {
+        self.inner.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut RelEntry> {
+        self.inner.get_mut(index)
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&RelEntry> {
+        self.position(name).map(|i| &self[i])
+    }
+
+    /// Makes the entries match `names`: entries whose name is still listed keep their stats,
+    /// entries whose name is missing are removed and new names are appended as fresh entries.
+    pub fn sync_names(&mut self, names: &[String]) -> SyncSummary {
+        let before = self.len();
+
+        self.retain(|e| names.contains(&e.name));
+        self.refresh_index();
+        let removed = before - self.len();
+        let kept = self.len();
+
+        for name in names {
+            if !self.contains(name) {
+                self.add(name.clone());
+            }
+        }
+
+        SyncSummary {
+            added: self.len() - kept,
+            removed,
+        }
+    }
+
+    /// Removes all entries and resets the session counters, skipped pairs and vote log, so the
+    /// list can be reused for the next batch.
+    ///
+    /// The allocated capacity of the entries is retained. Settings like the session limit,
+    /// autosave, the vote callback and the rng are kept as well.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.comparisons_done = 0;
+        self.skips = 0;
+        self.skipped.clear();
+        if let Some(log) = &mut self.vote_log {
+            log.clear();
+        }
+        self.refresh_index();
+    }
+
+    /// Keeps only the entries matching `predicate`, the inverse of `remove`.
+    ///
+    /// Equivalent to `self.retain(predicate)`, but part of the `RelVec` API itself.
+    pub fn keep<F: FnMut(&RelEntry) -> bool>(&mut self, predicate: F) {
+        self.retain(predicate);
+        self.refresh_index();
+    }
+
+    /// Sorts by percentage and removes every unlocked entry after the first `n`.
+    /// Locked entries are kept regardless of their rank. Returns the removed entries.
+    pub fn truncate_to_top(&mut self, n: usize) -> Vec<RelEntry> {
+        self.sort_percentage();
+
+        let (kept, removed) = self
+            .inner
+            .drain(..)
+            .enumerate()
+            .partition::<Vec<(usize, RelEntry)>, _>(|(i, e)| *i < n || e.locked);
+
+        self.inner = kept.into_iter().map(|(_, e)| e).collect();
+        self.refresh_index();
+        removed.into_iter().map(|(_, e)| e).collect()
+    }
+
+    /// Merges the entries of `other` into this list, matching them by name.
+    ///
+    /// Entries only in `other` are appended in their order, entries in both lists are
+    /// combined according to `strategy`. Fails with `Error::Overflow` if summed counters
+    /// would overflow, leaving the list unchanged.
+    pub fn merge_with(&mut self, other: &RelVec, strategy: MergeStrategy) -> Result<(), Error> {
+        if matches!(strategy, MergeStrategy::Sum) {
+            let mut sums: HashMap<&str, RelEntry> = HashMap::new();
+
+            for e in other.iter() {
+                match sums.get_mut(e.name.as_str()) {
+                    Some(sum) => sum.absorb(e.clone())?,
+                    None => {
+                        let mut sum = match self.position(&e.name) {
+                            Some(i) => self[i].clone(),
+                            None => RelEntry::new(e.name.clone(), 0, 0),
+                        };
+                        sum.absorb(e.clone())?;
+                        sums.insert(&e.name, sum);
+                    }
+                }
+            }
+        }
+
+        for e in other.iter() {
+            let i = match self.position(&e.name) {
+                Some(i) => i,
+                None => {
+                    if let Some(index) = &mut self.name_index {
+                        index.entry(e.name.clone()).or_insert(self.inner.len());
+                    }
+                    self.push(e.clone());
+                    continue;
+                }
+            };
+
+            match strategy {
+                MergeStrategy::Sum => self[i].absorb(e.clone())?,
+                MergeStrategy::KeepLeft => {}
+                MergeStrategy::KeepRight => self[i] = e.clone(),
+                MergeStrategy::Max => {
+                    if e.votes > self[i].votes {
+                        self[i] = e.clone();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds the first entry whose name equals `name` ignoring case.
+    ///
+    /// Only ASCII letters are case folded, so e.g. "Ä" and "ä" do not match.
+    pub fn position_ci(&self, name: &str) -> Option<usize> {
+        self.iter().position(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Indices of the entries whose name contains `query` ignoring case, best matches first.
+    ///
+    /// Names starting with `query` come first, the others are ordered by where the match
+    /// starts, and equal matches keep their order. Like `position_ci` only ASCII letters are
+    /// case folded.
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        let query = query.to_ascii_lowercase();
+        let mut matches: Vec<(usize, usize)> = self
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| e.name.to_ascii_lowercase().find(&query).map(|pos| (pos, i)))
+            .collect();
+
+        matches.sort_by_key(|&(pos, _)| pos);
+        matches.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Merges entries whose names are equal ignoring case into the first occurrence,
+    /// summing wins and votes. A merged entry is locked if any of its parts was.
+    ///
+    /// Only ASCII letters are case folded, so e.g. "Ä" and "ä" are kept apart. Fails with
+    /// `Error::Overflow` if summed counters would overflow, leaving the list unchanged.
+    pub fn dedup_ci(&mut self) -> Result<(), Error> {
+        let mut merged: Vec<RelEntry> = Vec::with_capacity(self.len());
+
+        for e in self.iter() {
+            match merged
+                .iter_mut()
+                .find(|m| m.name.eq_ignore_ascii_case(&e.name))
+            {
+                Some(m) => m.absorb(e.clone())?,
+                None => merged.push(e.clone()),
+            }
+        }
+
+        self.inner = merged;
+        self.refresh_index();
+        Ok(())
+    }
+
+    /// Records that `winner` won against `loser`, same as `vote_weighted(winner, loser, 1)`.
+    ///
+    /// Counters stay `u32`: if any of them would overflow, `Error::Overflow` is returned and
+    /// nothing is changed. Otherwise it fails only if an autosave is due and saving fails,
+    /// the vote itself is counted anyway.
+    pub fn vote(&mut self, winner: usize, loser: usize) -> Result<(), Error> {
+        self.vote_weighted(winner, loser, 1)
+    }
+
+    /// Records a win of `winner` against `loser` that counts `margin` times.
+    ///
+    /// `margin` is added to the wins of `winner` and to the votes of both, so a win with a
+    /// margin of 3 moves `percentage` exactly like three separate wins would. It still
+    /// counts as a single comparison for the session limit and autosave.
+    ///
+    /// Fails with `Error::SameEntry` if `winner == loser` and with `Error::ZeroMargin` if
+    /// `margin` is 0, without changing anything.
+    pub fn vote_weighted(&mut self, winner: usize, loser: usize, margin: u32) -> Result<(), Error> {
+        if winner == loser {
+            return Err(Error::SameEntry(self[winner].name.clone()));
+        }
+        if margin == 0 {
+            return Err(Error::ZeroMargin);
+        }
+
+        self.check_wins(winner, &[(loser, margin)])?;
+        if self[loser].votes.checked_add(margin).is_none() {
+            return Err(Error::Overflow(self[loser].name.clone()));
+        }
+
+        let before = self.comparisons_done;
+        self.count_vote(winner, loser, margin);
+        self.autosave_since(before)
+    }
+
+    /// Fails with `Error::Overflow` if `winner` can't take the given wins against each
+    /// `(loser, wins)`, checking its wins, votes and head-to-head records.
+    fn check_wins(&self, winner: usize, wins: &[(usize, u32)]) -> Result<(), Error> {
+        let w = &self[winner];
+        let total = wins
+            .iter()
+            .try_fold(0u32, |total, &(_, margin)| total.checked_add(margin));
+        let fits = total.is_some_and(|total| {
+            w.wins.checked_add(total).is_some() && w.votes.checked_add(total).is_some()
+        }) && wins.iter().all(|&(loser, margin)| {
+            w.wins_against(&self[loser].name)
+                .checked_add(margin)
+                .is_some()
+        });
+
+        if fits {
+            Ok(())
+        } else {
+            Err(Error::Overflow(w.name.clone()))
+        }
+    }
+
+    /// Applies a vote that was already checked for overflow, without autosaving.
+    fn count_vote(&mut self, winner: usize, loser: usize, margin: u32) {
+        let loser_name = self[loser].name.clone();
+
+        self[winner].wins += margin;
+        self[winner].votes += margin;
+        self[loser].votes += margin;
+        if let Some(log) = &mut self.vote_log {
+            log.push(VoteEntry {
+                winner: self.inner[winner].name.clone(),
+                loser: loser_name.clone(),
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs()),
+            });
+        }
+        *self[winner].beaten.entry(loser_name).or_insert(0) += margin;
+        self.comparisons_done += 1;
+
+        if let Some(callback) = &mut self.on_vote.0 {
+            callback(winner, loser);
+        }
+    }
+
+    /// Saves if an autosave became due since `comparisons_done` was `before`.
+    fn autosave_since(&self, before: usize) -> Result<(), Error> {
+        match &self.autosave {
+            Some((path, every)) if self.comparisons_done / every > before / every => {
+                self.save(path)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a win of `winner` against `loser` with a strength of preference from 1 (slight)
+    /// to 3 (strong), other values are clamped to that range.
+    ///
+    /// This is `vote_weighted` with `strength` as margin, so a strong win moves `percentage`
+    /// like three separate wins, while still counting as a single comparison.
+    pub fn vote_strength(
+        &mut self,
+        winner: usize,
+        loser: usize,
+        strength: u8,
+    ) -> Result<(), Error> {
+        self.vote_weighted(winner, loser, u32::from(strength.clamp(1, 3)))
+    }
+
+    /// Like `vote`, but takes the names of the entries instead of their indices.
+    ///
+    /// Fails with `Error::NotFound` if either name is missing and with `Error::SameEntry` if
+    /// both are the same.
+    pub fn vote_by_name(&mut self, winner: &str, loser: &str) -> Result<(), Error> {
+        if winner == loser {
+            return Err(Error::SameEntry(winner.to_string()));
+        }
+
+        let w = self
+            .position(winner)
+            .ok_or_else(|| Error::NotFound(winner.to_string()))?;
+        let l = self
+            .position(loser)
+            .ok_or_else(|| Error::NotFound(loser.to_string()))?;
+
+        self.vote(w, l)
+    }
+
+    /// Records that `winner` was picked as the best of a group, as if it had won a separate
+    /// comparison against each entry of `others`.
+    ///
+    /// So `winner` gets one win and one vote per other entry, every other entry gets one
+    /// vote, and each of these pairs counts as a comparison for the session limit and
+    /// autosave. `winner` itself is skipped if it is listed in `others`.
+    ///
+    /// Either all pairs are counted or, if any counter would overflow, none of them. The
+    /// autosave runs at most once, after the whole group.
+    pub fn vote_group(&mut self, winner: usize, others: &[usize]) -> Result<(), Error> {
+        let losers: Vec<usize> = others.iter().copied().filter(|&i| i != winner).collect();
+        let mut counts: Vec<(usize, u32)> = Vec::new();
+
+        for &loser in &losers {
+            match counts.iter_mut().find(|(i, _)| *i == loser) {
+                Some((_, c)) => *c = c.saturating_add(1),
+                None => counts.push((loser, 1)),
+            }
+        }
+
+        self.check_wins(winner, &counts)?;
+        if let Some(&(loser, _)) = counts
+            .iter()
+            .find(|&&(loser, c)| self[loser].votes.checked_add(c).is_none())
+        {
+            return Err(Error::Overflow(self[loser].name.clone()));
+        }
+
+        let before = self.comparisons_done;
+        for loser in losers {
+            self.count_vote(winner, loser, 1);
+        }
+
+        self.autosave_since(before)
+    }
+
+    /// Registers a callback that gets `(winner, loser)` after every `vote`, replacing any
+    /// previous one.
+    pub fn on_vote<F: FnMut(usize, usize) + 'static>(&mut self, callback: F) {
+        self.on_vote = VoteCallback(Some(Box::new(callback)));
+    }
+
+    pub fn clear_on_vote(&mut self) {
+        self.on_vote = VoteCallback(None);
+    }
+
+    /// Starts recording every vote with the names of both entries and the current time.
+    ///
+    /// The log is off by default and kept in memory only, use `save_log` to write it. An
+    /// already running log is kept.
+    pub fn enable_vote_log(&mut self) {
+        self.vote_log.get_or_insert_with(Vec::new);
+    }
+
+    /// Stops recording votes and drops the log.
+    pub fn disable_vote_log(&mut self) {
+        self.vote_log = None;
+    }
+
+    /// The recorded votes, oldest first. Empty while the log is turned off.
+    pub fn vote_log(&self) -> &[VoteEntry] {
+        self.vote_log.as_deref().unwrap_or(&[])
+    }
+
+    /// Writes the vote log as a JSON array.
+    pub fn save_log<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let writer = BufWriter::new(f);
+
+        serde_json::to_writer(writer, self.vote_log())?;
+        Ok(())
+    }
+
+    /// Replaces the vote log with the one in `file` as written by `save_log` and turns the
+    /// log on, so further votes are appended to it.
+    pub fn load_log<P: AsRef<Path>>(&mut self, file: P) -> Result<(), Error> {
+        let f = File::open(file)?;
+        let reader = BufReader::new(f);
+
+        self.vote_log = Some(serde_json::from_reader(reader)?);
+        Ok(())
+    }
+
+    /// Multiplies wins and votes of every entry by `factor`, rounding to whole numbers, so
+    /// older votes weigh less after repeated application.
+    ///
+    /// This is lossy: rounding discards information and cannot be undone. Head-to-head
+    /// records are not decayed.
+    pub fn apply_decay(&mut self, factor: f64) {
+        for e in self.iter_mut() {
+            e.wins = (f64::from(e.wins) * factor).round() as u32;
+            e.votes = ((f64::from(e.votes) * factor).round() as u32).max(e.wins);
+        }
+    }
+
+    /// Scales wins and votes of every voted entry so that it has exactly `target` votes,
+    /// keeping its percentage as well as whole numbers allow (wins are rounded).
+    ///
+    /// Like `apply_decay` this is lossy and changes the stored counts for good. Entries without
+    /// votes and the head-to-head records are left unchanged.
+    pub fn normalize_votes(&mut self, target: u32) {
+        for e in self.iter_mut().filter(|e| e.votes > 0) {
+            let votes = u64::from(e.votes);
+
+            e.wins = ((u64::from(e.wins) * u64::from(target) + votes / 2) / votes) as u32;
+            e.votes = target;
+        }
+    }
+
+    /// Saves to `path` after every `every` votes, `every == 0` turns autosaving off.
+    ///
+    /// Every autosave rewrites the whole file, so small intervals on large lists slow down
+    /// voting noticeably.
+    pub fn enable_autosave<P: AsRef<Path>>(&mut self, path: P, every: usize) {
+        self.autosave = if every == 0 {
+            None
+        } else {
+            Some((path.as_ref().to_path_buf(), every))
+        };
+    }
+
+    pub fn disable_autosave(&mut self) {
+        self.autosave = None;
+    }
+
+    pub fn comparisons_done(&self) -> usize {
+        self.comparisons_done
+    }
+
+    /// Skips the comparison of `a` and `b` without recording a vote.
+    ///
+    /// Wins and votes stay unchanged and the skip does not count towards the session limit.
+    /// The pair is avoided by `choose_pair` for the next rounds, but offered again after
+    /// that or if the strategy keeps choosing it.
+    pub fn skip(&mut self, a: usize, b: usize) {
+        let round = self.comparisons_done + self.skips;
+        let (a, b) = skip_key(&self[a].name, &self[b].name);
+        let (a, b) = (a.to_string(), b.to_string());
+
+        self.skipped
+            .retain(|(x, y, r)| round - r < SKIP_COOLDOWN && (*x != a || *y != b));
+        self.skipped.push((a, b, round));
+        self.skips += 1;
+    }
+
+    /// Number of skipped comparisons in this session.
+    pub fn skips(&self) -> usize {
+        self.skips
+    }
+
+    /// Whether `a` and `b` have been skipped within the last rounds, see `skip`.
+    pub fn recently_skipped(&self, a: usize, b: usize) -> bool {
+        let round = self.comparisons_done + self.skips;
+        let (a, b) = skip_key(&self[a].name, &self[b].name);
+
+        self.skipped
+            .iter()
+            .any(|(x, y, r)| x == a && y == b && round - r < SKIP_COOLDOWN)
+    }
+
+    /// Asks `choose` for a pair, asking again a few times if it returns a recently skipped
+    /// pair.
+    pub fn choose_pair<F: FnMut(&mut RelVec) -> Option<(usize, usize)>>(
+        &mut self,
+        mut choose: F,
+    ) -> Option<(usize, usize)> {
+        let mut pair = choose(self);
+
+        for _ in 0..SKIP_RETRIES {
+            match pair {
+                Some((a, b)) if self.recently_skipped(a, b) => pair = choose(self),
+                _ => break,
+            }
+        }
+
+        pair
+    }
+
+    pub fn session_complete(&self) -> bool {
+        matches!(self.session_limit, Some(limit) if self.comparisons_done >= limit)
+    }
+
+    pub fn remaining_comparisons(&self) -> Option<usize> {
+        self.session_limit
+            .map(|limit| limit.saturating_sub(self.comparisons_done))
+    }
+
+    /// Rough number of comparisons still needed to rank the unlocked entries.
+    ///
+    /// This is an estimate for a merge-sort-style ranking, `ceil(n * log2(n))` for `n`
+    /// unlocked entries, not the number of all pairs. Comparisons already done are taken
+    /// from the stored votes of the unlocked entries, so earlier sessions count as well.
+    pub fn estimated_remaining_comparisons(&self) -> usize {
+        let n = self.active_len() as f64;
+        let needed = if n > 1.0 {
+            (n * n.log2()).ceil() as usize
+        } else {
+            0
+        };
+        let done = self
+            .iter()
+            .filter(|e| e.is_pairable())
+            .map(|e| e.votes as usize)
+            .sum::<usize>()
+            / 2;
+
+        needed.saturating_sub(done)
+    }
+
+    /// Predicted chances of `a` and `b` winning against each other, summing up to 1.
+    ///
+    /// The percentages of both are normalized, so 75% against 25% gives `(0.75, 0.25)`
+    /// and 60% against 20% gives `(0.75, 0.25)` as well. If one of them has no votes, or both
+    /// are at 0%, the prediction is `(0.5, 0.5)`.
+    pub fn predicted_winner(&self, a: usize, b: usize) -> (f64, f64) {
+        if self[a].votes == 0 || self[b].votes == 0 {
+            return (0.5, 0.5);
+        }
+
+        let (pa, pb) = (self[a].percentage(), self[b].percentage());
+
+        if pa + pb == 0.0 {
+            return (0.5, 0.5);
+        }
+
+        (pa / (pa + pb), pb / (pa + pb))
+    }
+
+    /// Expected information from comparing `a` and `b`, between 0 and 1 bit.
+    ///
+    /// This is the binary entropy `-p * log2(p) - q * log2(q)` of the outcome predicted by
+    /// `predicted_winner`. An even matchup (or one with an unvoted entry) is the most
+    /// uncertain and gives 1, the more lopsided the prediction, the less a comparison is
+    /// expected to tell, down to 0 for a certain outcome.
+    ///
+    /// `informative_pair` does not use this, it keeps its own score that also takes the
+    /// number of votes into account.
+    pub fn info_gain(&self, a: usize, b: usize) -> f64 {
+        let (p, q) = self.predicted_winner(a, b);
+        let h = |x: f64| if x > 0.0 { -x * x.log2() } else { 0.0 };
+
+        h(p) + h(q)
+    }
+
+    /// Returns how often `a` won against `b` and how often `b` won against `a`.
+    pub fn head_to_head(&self, a: usize, b: usize) -> (u32, u32) {
+        (
+            self[a].wins_against(&self[b].name),
+            self[b].wins_against(&self[a].name),
+        )
+    }
+
+    /// Every pair of entries available for pairing (see `reduced`) exactly once, ordered by
+    /// the first and then the second index (always `a < b`).
+    ///
+    /// Unlike `remaining_pairs` this ignores earlier comparisons, so walking through the
+    /// result plays a full round-robin tournament.
+    pub fn round_robin(&self) -> Vec<(usize, usize)> {
+        let reduced = self.reduced();
+        let mut v = Vec::with_capacity(reduced.len() * reduced.len().saturating_sub(1) / 2);
+
+        for (x, &a) in reduced.iter().enumerate() {
+            for &b in &reduced[x + 1..] {
+                v.push((a, b));
+            }
+        }
+
+        v
+    }
+
+    /// Head-to-head wins of every entry against every other: `matrix[i][j]` is how often
+    /// entry `i` won against entry `j`. The diagonal is always 0.
+    pub fn win_matrix(&self) -> Vec<Vec<u32>> {
+        (0..self.len())
+            .map(|i| {
+                (0..self.len())
+                    .map(|j| if i == j { 0 } else { self.head_to_head(i, j).0 })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Saves `win_matrix` as CSV with the names as first row and column, so each row lists
+    /// the wins of that entry against the entries of the columns.
+    pub fn save_matrix_csv<P: AsRef<Path>>(&self, file: P) -> Result<(), Error> {
+        let f = File::create(file)?;
+        let mut writer = BufWriter::new(f);
+
+        for e in self.iter() {
+            write!(writer, ",{}", csv_escape(&e.name))?;
+        }
+        writeln!(writer)?;
+
+        for (e, row) in self.iter().zip(self.win_matrix()) {
+            write!(writer, "{}", csv_escape(&e.name))?;
+            for count in row {
+                write!(writer, ",{}", count)?;
+            }
+            writeln!(writer)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Lists every pair of unlocked entries that has not been compared yet, ordered by
+    /// the first and then the second index (always `a < b`).
+    pub fn remaining_pairs(&self) -> Vec<(usize, usize)> {
+        let reduced = self.reduced();
+        let mut v = Vec::new();
+
+        for (x, &a) in reduced.iter().enumerate() {
+            for &b in &reduced[x + 1..] {
+                if self.head_to_head(a, b) == (0, 0) {
+                    v.push((a, b));
+                }
+            }
+        }
+
+        v
+    }
+
+    /// Borda-style score of every entry: the sum of its head-to-head wins against the other
+    /// entries of the list.
+    ///
+    /// Unlike the percentage this is not divided by the number of votes, so entries that
+    /// were compared more often and beat more opponents score higher. Wins against entries
+    /// no longer in the list are not counted.
+    pub fn borda_scores(&self) -> Vec<u32> {
+        let names: HashSet<&str> = self.iter().map(|e| e.name.as_str()).collect();
+
+        self.iter()
+            .map(|e| {
+                e.beaten
+                    .iter()
+                    .filter(|(name, _)| *name != &e.name && names.contains(name.as_str()))
+                    .map(|(_, c)| c)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Sorts by descending `borda_scores`, ties keep their order.
+    pub fn sort_borda(&mut self) {
+        let scores = self.borda_scores();
+        let mut entries: Vec<(u32, RelEntry)> =
+            scores.into_iter().zip(self.inner.drain(..)).collect();
+
+        entries.sort_by_key(|(score, _)| Reverse(*score));
+        self.inner = entries.into_iter().map(|(_, e)| e).collect();
+        self.refresh_index();
+    }
+
+    /// Sorts by descending `smoothed_percentage(alpha)`.
+    pub fn sort_smoothed(&mut self, alpha: f64) {
+        self.sort_by_score(|e| e.smoothed_percentage(alpha))
+    }
+
+    /// Sorts by descending `lower_ci(z)`, entries without votes come last.
+    pub fn sort_by_lower_ci(&mut self, z: f64) {
+        self.sort_by_score(|e| e.lower_ci(z).unwrap_or(f64::NAN))
+    }
+
+    /// Sorts by descending `score`. Entries scoring NaN come last, ties keep their order.
+    pub fn sort_by_score<F: Fn(&RelEntry) -> f64>(&mut self, score: F) {
+        self.sort_by(|a, b| {
+            let (a, b) = (score(a), score(b));
+
+            match (a.is_nan(), b.is_nan()) {
+                (false, false) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+                (false, true) => Ordering::Less,
+                (true, false) => Ordering::Greater,
+                (true, true) => Ordering::Equal,
+            }
+        });
+        self.refresh_index();
+    }
+
+    /// Sorts by name using plain `str` ordering, i.e. by byte value ("B" < "a", "a10" < "a2").
+    pub fn sort_name(&mut self) {
+        self.sort_by(|a, b| a.name.cmp(&b.name));
+        self.refresh_index();
+    }
+
+    /// Like `sort_name`, but ignores ASCII case. Entries with equal names keep their order.
+    pub fn sort_name_ci(&mut self) {
+        self.sort_by(|a, b| {
+            a.name
+                .bytes()
+                .map(|c| c.to_ascii_lowercase())
+                .cmp(b.name.bytes().map(|c| c.to_ascii_lowercase()))
+        });
+        self.refresh_index();
+    }
+
+    /// Sorts by name, comparing runs of digits by their numeric value ("Episode 2" <
+    /// "Episode 10"). Runs with the same value are ordered by their length, so "1" < "01".
+    pub fn sort_name_natural(&mut self) {
+        self.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+        self.refresh_index();
+    }
+
+    /// Whether every pair of unlocked entries has been compared at least `min_per_pair`
+    /// times. Always true with less than two unlocked entries.
+    pub fn is_complete(&self, min_per_pair: u32) -> bool {
+        let reduced = self.reduced();
+
+        reduced.iter().enumerate().all(|(x, &a)| {
+            reduced[x + 1..].iter().all(|&b| {
+                let (w, l) = self.head_to_head(a, b);
+                w + l >= min_per_pair
+            })
+        })
+    }
+
+    /// Returns the entry that won the head-to-head record against every other entry.
+    ///
+    /// There is none if the preferences are cyclic or some pairs were never compared.
+    pub fn condorcet_winner(&self) -> Option<usize> {
+        (0..self.len()).find(|&a| {
+            (0..self.len())
+                .filter(|&b| b != a)
+                .all(|b| matches!(self.head_to_head(a, b), (w, l) if w > l))
+        })
+    }
+
+    /// Majority preference graph: `graph[a]` lists every `b` that `a` won against more often
+    /// than it lost against.
+    fn majority_graph(&self) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .map(|a| {
+                (0..self.len())
+                    .filter(|&b| b != a)
+                    .filter(|&b| matches!(self.head_to_head(a, b), (w, l) if w > l))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Whether the majority head-to-head preferences contain a cycle (A > B > C > A).
+    pub fn has_cycle(&self) -> bool {
+        !self.cycles().is_empty()
+    }
+
+    /// Share of the triples of entries with a decided majority preference between all three
+    /// pairs that are transitive, i.e. not a cycle like A > B > C > A.
+    ///
+    /// Pairs that never met or are tied don't count, and without any fully decided triple
+    /// the score is 1.0.
+    pub fn transitivity_score(&self) -> f64 {
+        let n = self.len();
+        let mut beats = vec![vec![false; n]; n];
+
+        for (a, edges) in self.majority_graph().into_iter().enumerate() {
+            for b in edges {
+                beats[a][b] = true;
+            }
+        }
+
+        let decided = |a: usize, b: usize| beats[a][b] || beats[b][a];
+        let (mut triples, mut transitive) = (0usize, 0usize);
+
+        for a in 0..n {
+            for b in a + 1..n {
+                if !decided(a, b) {
+                    continue;
+                }
+                for c in b + 1..n {
+                    if !decided(a, c) || !decided(b, c) {
+                        continue;
+                    }
+
+                    triples += 1;
+                    let cyclic = (beats[a][b] && beats[b][c] && beats[c][a])
+                        || (beats[b][a] && beats[c][b] && beats[a][c]);
+                    if !cyclic {
+                        transitive += 1;
+                    }
+                }
+            }
+        }
+
+        if triples == 0 {
+            1.0
+        } else {
+            transitive as f64 / triples as f64
+        }
+    }
+
+    /// Entries whose head-to-head record contradicts their percentage: of all comparisons
+    /// against entries with a lower percentage, more than `threshold` (0 to 1) were lost.
+    ///
+    /// Entries without votes and entries that never met a lower-ranked one are left out.
+    pub fn inconsistent_entries(&self, threshold: f64) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&a| self[a].votes > 0)
+            .filter(|&a| {
+                let (mut won, mut lost) = (0u64, 0u64);
+
+                for b in (0..self.len()).filter(|&b| b != a && self[b].votes > 0) {
+                    if self[b].compare_percentage(&self[a]) == Ordering::Less {
+                        let (w, l) = self.head_to_head(a, b);
+                        won += u64::from(w);
+                        lost += u64::from(l);
+                    }
+                }
+
+                won + lost > 0 && lost as f64 / (won + lost) as f64 > threshold
+            })
+            .collect()
+    }
+
+    /// Groups of entries whose majority head-to-head preferences are cyclic.
+    ///
+    /// Each group is a strongly connected component of the preference graph, i.e. every
+    /// entry of a group can be reached from every other one by following "won against"
+    /// edges. Groups and the indices inside them are sorted ascending. Self-loops (e.g.
+    /// from duplicate names) are ignored.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let graph = self.majority_graph();
+        let n = graph.len();
+
+        // Kosaraju: finish order on the graph, then collect components on the reverse graph
+        let mut order = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut stack = vec![(start, 0)];
+
+            while let Some((node, next)) = stack.pop() {
+                if let Some(&succ) = graph[node].get(next) {
+                    stack.push((node, next + 1));
+                    if !visited[succ] {
+                        visited[succ] = true;
+                        stack.push((succ, 0));
+                    }
+                } else {
+                    order.push(node);
+                }
+            }
+        }
+
+        let mut reverse = vec![Vec::new(); n];
+        for (a, succs) in graph.iter().enumerate() {
+            for &b in succs {
+                reverse[b].push(a);
+            }
+        }
+
+        let mut assigned = vec![false; n];
+        let mut cycles = Vec::new();
+
+        for &start in order.iter().rev() {
+            if assigned[start] {
+                continue;
+            }
+            assigned[start] = true;
+            let mut component = vec![start];
+            let mut stack = vec![start];
+
+            while let Some(node) = stack.pop() {
+                for &pred in &reverse[node] {
+                    if !assigned[pred] {
+                        assigned[pred] = true;
+                        component.push(pred);
+                        stack.push(pred);
+                    }
+                }
+            }
+
+            if component.len() > 1 {
+                component.sort_unstable();
+                cycles.push(component);
+            }
+        }
+
+        cycles.sort();
+        cycles
+    }
+
+    /// Sorts by descending percentage, entries without votes come last.
+    pub fn sort_percentage(&mut self) {
+        self.sort_by_score(RelEntry::percentage)
+    }
+
+    /// Sorts by descending percentage like `sort_percentage`, but entries with equal
+    /// percentages are ordered by descending votes (see
+    /// `RelEntry::compare_percentage_then_votes`). Entries without votes come last.
+    pub fn sort_percentage_confident(&mut self) {
+        self.sort_by(|a, b| {
+            (b.votes > 0)
+                .cmp(&(a.votes > 0))
+                .then_with(|| b.compare_percentage_then_votes(a))
+        });
+        self.refresh_index();
+    }
+
+    /// Sorts by percentage, but keeps all locked entries above the unlocked ones.
+    pub fn sort_percentage_locked_first(&mut self) {
+        self.sort_percentage();
+        self.sort_by_key(|e| !e.locked);
+        self.refresh_index();
+    }
+
+    /// Counts the voted entries per percentage bucket, lowest bucket first.
+    ///
+    /// Buckets are half-open (`[0, 50)`, `[50, 100]` for two buckets), except for the last
+    /// one which also contains 100%.
+    pub fn histogram(&self, buckets: usize) -> Vec<usize> {
+        let mut v = vec![0; buckets];
+
+        if buckets == 0 {
+            return v;
+        }
+
+        for e in self.iter().filter(|e| e.votes > 0) {
+            let i = (u64::from(e.wins) * buckets as u64 / u64::from(e.votes)) as usize;
+            v[i.min(buckets - 1)] += 1;
+        }
+
+        v
+    }
+
+    /// Groups the unlocked entries into tiers by percentage, e.g. `&[90.0, 70.0, 50.0]` for
+    /// S (>= 90%), A (>= 70%), B (>= 50%) and C (the rest).
+    ///
+    /// Returns `boundaries.len() + 1` tiers, highest first, followed by a trailing group of the
+    /// unlocked entries without votes. Within a tier the entries are ordered by percentage.
+    pub fn tiers(&self, boundaries: &[f64]) -> Vec<Vec<usize>> {
+        let mut boundaries = boundaries.to_vec();
+        boundaries.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+        let mut tiers = vec![Vec::new(); boundaries.len() + 2];
+        let (mut voted, unvoted): (Vec<usize>, Vec<usize>) = self
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.locked)
+            .map(|(i, _)| i)
+            .partition(|&i| self[i].votes > 0);
+        voted.sort_by(|&a, &b| self[b].compare_percentage(&self[a]));
+
+        for i in voted {
+            let p = self[i].percentage();
+            let tier = boundaries
+                .iter()
+                .position(|&b| p >= b)
+                .unwrap_or(boundaries.len());
+
+            tiers[tier].push(i);
+        }
+        tiers[boundaries.len() + 1] = unvoted;
+
+        tiers
+    }
+
+    /// Share (0 to 100) of the other voted entries that have a strictly lower percentage.
+    ///
+    /// Entries without votes are left out of the comparison and get a percentile of 0.
+    pub fn percentile(&self, index: usize) -> f64 {
+        let entry = &self[index];
+
+        if entry.votes == 0 {
+            return 0.0;
+        }
+
+        let others: Vec<&RelEntry> = self
+            .iter()
+            .enumerate()
+            .filter(|(i, e)| *i != index && e.votes > 0)
+            .map(|(_, e)| e)
+            .collect();
+        let lower = others
+            .iter()
+            .filter(|e| entry.compare_percentage(e) == Ordering::Greater)
+            .count();
+
+        if others.is_empty() {
+            0.0
+        } else {
+            lower as f64 * 100.0 / others.len() as f64
+        }
+    }
+
+    /// Returns the entries by descending percentage together with their rank, starting at 1.
+    ///
+    /// Ties share a rank and the following rank is skipped ("1, 2, 2, 4"). Entries without
+    /// votes come last and all share the rank after the last voted entry.
+    pub fn ranked(&self) -> Vec<(usize, &RelEntry)> {
+        rank_entries(&self.inner)
+    }
+
+    /// Like `ranked`, but lazy and with ranks starting at 0.
+    ///
+    /// Only the indices are sorted up front, the entries are neither cloned nor collected.
+    pub fn ranked_iter(&self) -> impl Iterator<Item = (usize, &RelEntry)> {
+        let mut order: Vec<usize> = (0..self.len()).filter(|&i| self[i].votes > 0).collect();
+        order.sort_by(|&a, &b| self[b].compare_percentage(&self[a]));
+        let voted = order.len();
+        order.extend((0..self.len()).filter(|&i| self[i].votes == 0));
+
+        let mut prev: Option<(usize, &RelEntry)> = None;
+
+        order.into_iter().enumerate().map(move |(n, i)| {
+            let e = &self.inner[i];
+            let rank = match prev {
+                _ if n >= voted => voted,
+                Some((rank, p)) if p.compare_percentage(e) == Ordering::Equal => rank,
+                _ => n,
+            };
+
+            prev = Some((rank, e));
+            (rank, e)
+        })
+    }
+
+    /// The voted entry with the highest percentage, found in a single pass without sorting.
+    ///
+    /// Ties go to the entry with more votes and then to the alphabetically first name.
+    /// Returns `None` if no entry has votes.
+    pub fn leader(&self) -> Option<&RelEntry> {
+        self.iter().filter(|e| e.votes > 0).min_by(|a, b| {
+            b.compare_percentage(a)
+                .then_with(|| b.votes.cmp(&a.votes))
+                .then_with(|| a.name.cmp(&b.name))
+        })
+    }
+
+    /// Renders the voted entries as an SVG bar chart, best first, with one horizontal bar per
+    /// entry. A bar spanning the full `width` stands for 100%.
+    pub fn to_svg(&self, width: u32, height: u32) -> String {
+        let voted: Vec<&RelEntry> = self
+            .ranked()
+            .into_iter()
+            .map(|(_, e)| e)
+            .filter(|e| e.votes > 0)
+            .collect();
+        let bar_height = f64::from(height) / voted.len().max(1) as f64;
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+            width, height
+        )
+        .expect("writing to a String does not fail");
+
+        for (i, e) in voted.iter().enumerate() {
+            let y = i as f64 * bar_height;
+            writeln!(
+                svg,
+                "<rect x=\"0\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"steelblue\"/>",
+                y,
+                e.percentage_clamped() / 100.0 * f64::from(width),
+                bar_height * 0.9
+            )
+            .expect("writing to a String does not fail");
+            writeln!(
+                svg,
+                "<text x=\"4\" y=\"{:.2}\" dominant-baseline=\"middle\">{}</text>",
+                y + bar_height * 0.45,
+                xml_escape(&e.name)
+            )
+            .expect("writing to a String does not fail");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Counts the entries whose rank (see `ranked`) differs from the one in `previous`,
+    /// matching entries by name. Entries only present on one side count as changed.
+    pub fn rank_changed_since(&self, previous: &[RelEntry]) -> usize {
+        let before: HashMap<&str, usize> = rank_entries(previous)
+            .into_iter()
+            .map(|(rank, e)| (e.name.as_str(), rank))
+            .collect();
+        let now = self.ranked();
+
+        let changed = now
+            .iter()
+            .filter(|(rank, e)| before.get(e.name.as_str()) != Some(rank))
+            .count();
+        let removed = previous
+            .iter()
+            .filter(|p| !now.iter().any(|(_, e)| e.name == p.name))
+            .count();
+
+        changed + removed
+    }
+
+    /// Locks every entry with at least `min_votes` votes and a percentage of at least `pct`.
+    /// Returns how many entries were newly locked.
+    pub fn lock_above(&mut self, pct: f64, min_votes: u32) -> usize {
+        let mut count = 0;
+
+        for e in self.iter_mut() {
+            if !e.locked && e.votes > 0 && e.votes >= min_votes && e.percentage() >= pct {
+                e.locked = true;
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Unlocks every voted entry with a percentage below `pct`. Returns how many entries
+    /// were unlocked.
+    pub fn unlock_below(&mut self, pct: f64) -> usize {
+        let mut count = 0;
+
+        for e in self.iter_mut() {
+            if e.locked && e.votes > 0 && e.percentage() < pct {
+                e.locked = false;
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Computes several summary statistics in a single pass over the entries.
+    pub fn stats(&self) -> RelStats {
+        let (mut unlocked, mut total_votes, mut voted, mut sum) = (0, 0u64, 0, 0.0);
+
+        for e in self.iter() {
+            if !e.locked {
+                unlocked += 1;
+            }
+            if e.votes > 0 {
+                voted += 1;
+                sum += e.percentage();
+            }
+            total_votes += u64::from(e.votes);
+        }
+
+        RelStats {
+            entries: self.len(),
+            unlocked,
+            total_votes,
+            mean_percentage: if voted > 0 {
+                Some(sum / voted as f64)
+            } else {
+                None
+            },
+            coverage: if self.is_empty() {
+                0.0
+            } else {
+                voted as f64 / self.len() as f64
+            },
+        }
+    }
+
+    /// Number of entries available for pairing, same as `reduced().len()` without
+    /// allocating.
+    pub fn active_len(&self) -> usize {
+        self.iter().filter(|e| e.is_pairable()).count()
+    }
+
+    pub fn locked_len(&self) -> usize {
+        self.iter().filter(|e| e.locked).count()
+    }
+
+    /// Entries available for pairing, i.e. neither locked nor disabled and with a positive
+    /// weight.
+    pub fn reduced(&self) -> Vec<usize> {
+        self.inner
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.is_pairable())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn unvoted(&self) -> Vec<usize> {
+        self.inner
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.is_pairable() && e.votes == 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Entries that won every one of their votes. Entries without votes are left out.
+    pub fn undefeated(&self) -> Vec<usize> {
+        self.inner
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.votes > 0 && e.wins == e.votes)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Entries that lost every one of their votes. Entries without votes are left out.
+    pub fn winless(&self) -> Vec<usize> {
+        self.inner
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.votes > 0 && e.wins == 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn min_votes(&self) -> Vec<usize> {
+        let mut min = u32::MAX;
+        let mut v = Vec::new();
+
+        for i in 0..self.len() {
+            if !self[i].is_pairable() {
+                continue;
+            }
+            match self[i].votes.cmp(&min) {
+                Ordering::Less => {
+                    min = self[i].votes;
+                    v = Vec::new();
+                    v.push(i);
+                }
+                Ordering::Equal => {
+                    v.push(i);
+                }
+                Ordering::Greater => {}
+            }
+        }
+
+        v
+    }
+
+    /// Chooses the next pair using `strategy`.
+    ///
+    /// Recently skipped pairs are avoided, see `choose_pair`.
+    pub fn next_pair(&mut self, strategy: &VoteStrategy) -> Option<(usize, usize)> {
+        self.choose_pair(strategy.choose_function())
+    }
+
+    /// Like `next_pair`, but returns a `Matchup` instead of a tuple.
+    pub fn next_matchup(&mut self, strategy: &VoteStrategy) -> Option<Matchup> {
+        self.next_pair(strategy).map(Matchup::from)
+    }
+
+    /// Starts a `Session` that picks pairs with `strategy`.
+    pub fn session(&mut self, strategy: VoteStrategy) -> Session<'_> {
+        Session::new(self, strategy)
+    }
+
+    /// Starts a best-of matchup between `a` and `b` that applies a single vote once one of
+    /// them won `wins_needed` games, e.g. 2 for best of 3. See `BestOfSession`.
+    ///
+    /// Fails with `Error::SameEntry` if `a == b`.
+    pub fn best_of(
+        &mut self,
+        a: usize,
+        b: usize,
+        wins_needed: u32,
+    ) -> Result<BestOfSession<'_>, Error> {
+        BestOfSession::new(self, a, b, wins_needed)
+    }
+
+    pub fn random_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.random_pair_with(rng))
+    }
+
+    /// Picks two distinct unlocked entries, each with a chance proportional to its weight.
+    ///
+    /// The first entry is drawn from all candidates and the second from the remaining ones,
+    /// so with equal weights every unordered pair is equally likely (`2 / (n * (n - 1))`).
+    pub fn random_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let mut candidates = self.reduced();
+
+        if candidates.len() < 2 {
+            return None;
+        }
+
+        let i1 = candidates.remove(self.weighted_index(rng, &candidates));
+        let i2 = candidates[self.weighted_index(rng, &candidates)];
+
+        Some((i1, i2))
+    }
+
+    fn weighted_index<R: Rng>(&self, rng: &mut R, candidates: &[usize]) -> usize {
+        let total: f64 = candidates.iter().map(|&i| self[i].pair_weight()).sum();
+        let mut x = rng.gen::<f64>() * total;
+
+        for (n, &i) in candidates.iter().enumerate() {
+            x -= self[i].pair_weight();
+            if x < 0.0 {
+                return n;
+            }
+        }
+
+        candidates.len() - 1
+    }
+
+    pub fn min_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.min_pair_with(rng))
+    }
+
+    pub fn min_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let reduced = self.reduced();
+        let mins = self.min_votes();
+        if reduced.len() < 2 {
+            return None;
+        }
+
+        let i1 = mins[rng.gen_range(0..mins.len())];
+        let p1 = reduced.iter().position(|&i| i == i1).unwrap_or(0);
+        let p2 = rng.gen_range(0..(reduced.len() - 1));
+        if p2 >= p1 {
+            Some((i1, reduced[p2 + 1]))
+        } else {
+            Some((i1, reduced[p2]))
+        }
+    }
+
+    /// Deterministic version of `min_pair`: the first unlocked entry with the fewest votes
+    /// and, among the other unlocked entries, the first one with the fewest votes.
+    pub fn min_pair_first(&self) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let i1 = *self.min_votes().first()?;
+        let i2 = self
+            .reduced()
+            .into_iter()
+            .filter(|&i| i != i1)
+            .min_by_key(|&i| self[i].votes)?;
+
+        Some((i1, i2))
+    }
+
+    pub fn equal_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.equal_pair_with(rng))
+    }
+
+    /// Chooses two unlocked entries with exactly the same percentage.
+    ///
+    /// Percentages are compared as fractions (`w1 * v2 == w2 * v1`), not as floats, so 3/4
+    /// and 6/8 are always equal. Entries without votes are never paired.
+    pub fn equal_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        self.equal_pair_by(rng, |a, b| {
+            u64::from(a.wins) * u64::from(b.votes) == u64::from(b.wins) * u64::from(a.votes)
+        })
+    }
+
+    /// Deterministic version of `equal_pair`: the pair of unlocked, voted entries with exactly
+    /// the same percentage with the lowest first and then the lowest second index.
+    pub fn equal_pair_first(&self) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let reduced: Vec<usize> = self
+            .reduced()
+            .into_iter()
+            .filter(|&i| self[i].votes > 0)
+            .collect();
+
+        for (x, &a) in reduced.iter().enumerate() {
+            for &b in &reduced[x + 1..] {
+                let (ea, eb) = (&self[a], &self[b]);
+
+                if u64::from(ea.wins) * u64::from(eb.votes)
+                    == u64::from(eb.wins) * u64::from(ea.votes)
+                {
+                    return Some((a, b));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn equal_pair_tol(&mut self, tol: f64) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.equal_pair_tol_with(tol, rng))
+    }
+
+    /// Like `equal_pair_with`, but treats percentages differing by at most `tol` (in
+    /// percentage points) as equal.
+    pub fn equal_pair_tol_with<R: Rng>(&self, tol: f64, rng: &mut R) -> Option<(usize, usize)> {
+        self.equal_pair_by(rng, |a, b| (a.percentage() - b.percentage()).abs() <= tol)
+    }
+
+    fn equal_pair_by<R: Rng, F: Fn(&RelEntry, &RelEntry) -> bool>(
+        &self,
+        rng: &mut R,
+        equal: F,
+    ) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let mut reduced = self.reduced();
+        reduced.retain(|&i| self[i].votes > 0);
+
+        if reduced.len() < 2 {
+            return None;
+        }
+
+        reduced.shuffle(rng);
+
+        for i1 in 0..reduced.len() {
+            for i2 in i1 + 1..reduced.len() {
+                if equal(&self[reduced[i1]], &self[reduced[i2]]) {
+                    return Some((reduced[i1], reduced[i2]));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn nearest_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.nearest_pair_with(rng))
+    }
+
+    pub fn nearest_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let mut reduced = self.reduced();
+
+        if reduced.len() < 2 {
+            return None;
+        }
+
+        reduced.shuffle(rng);
+
+        let mut min = None;
+
+        for i1 in 0..reduced.len() {
+            for i2 in i1 + 1..reduced.len() {
+                let d2 = (self[reduced[i2]].percentage() - self[reduced[i1]].percentage()).abs();
+                match min {
+                    Some((_, _, d)) => {
+                        if d2 < d {
+                            min = Some((i1, i2, d2));
+                        }
+                    }
+                    None => {
+                        min = Some((i1, i2, d2));
+                    }
+                }
+            }
+        }
+
+        min.map(|(a, b, _)| (reduced[a], reduced[b]))
+    }
+
+    pub fn soft_nearest_pair(&mut self, temperature: f64) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.soft_nearest_pair_with(temperature, rng))
+    }
+
+    /// Randomly picks a pair of unlocked entries, preferring pairs with close percentages.
+    ///
+    /// A pair whose percentages are `d` points further apart than those of the nearest pair is
+    /// chosen with a relative chance of `exp(-d / temperature)`. So a low temperature almost
+    /// always gives the nearest pair (exactly for `temperature <= 0`) and a high temperature
+    /// gives every pair about the same chance. Entries without votes count as 50%. The lower
+    /// index comes first.
+    pub fn soft_nearest_pair_with<R: Rng>(
+        &self,
+        temperature: f64,
+        rng: &mut R,
+    ) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let reduced = self.reduced();
+        let percentage = |i: usize| {
+            if self[i].votes == 0 {
+                50.0
+            } else {
+                self[i].percentage()
+            }
+        };
+        let distance = |a: usize, b: usize| (percentage(a) - percentage(b)).abs();
+        let mut min = f64::INFINITY;
+
+        for (x, &a) in reduced.iter().enumerate() {
+            for &b in &reduced[x + 1..] {
+                min = min.min(distance(a, b));
+            }
+        }
+
+        weighted_pair(&reduced, rng, |a, b| {
+            let d = distance(a, b);
+
+            if temperature > 0.0 {
+                (-(d - min) / temperature).exp()
+            } else if d == min {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    pub fn informative_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.informative_pair_with(rng))
+    }
+
+    /// Chooses the unlocked pair that is both close and uncertain.
+    ///
+    /// Every pair is scored with `(100 - |p1 - p2|) / (v1 + v2 + 1)`, where `p` is the
+    /// percentage (50 for entries without votes) and `v` the number of votes, and the pair
+    /// with the highest score is returned. Ties are broken randomly.
+    pub fn informative_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let mut reduced = self.reduced();
+
+        if reduced.len() < 2 {
+            return None;
+        }
+
+        reduced.shuffle(rng);
+
+        let percentage = |e: &RelEntry| if e.votes == 0 { 50.0 } else { e.percentage() };
+        let mut best: Option<(usize, usize, f64)> = None;
+
+        for i1 in 0..reduced.len() {
+            for i2 in i1 + 1..reduced.len() {
+                let (a, b) = (&self[reduced[i1]], &self[reduced[i2]]);
+                let closeness = 100.0 - (percentage(a) - percentage(b)).abs();
+                let score = closeness / (f64::from(a.votes) + f64::from(b.votes) + 1.0);
+
+                if best.map_or(true, |(_, _, s)| score > s) {
+                    best = Some((i1, i2, score));
+                }
+            }
+        }
+
+        best.map(|(a, b, _)| (reduced[a], reduced[b]))
+    }
+
+    pub fn unvoted_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.unvoted_pair_with(rng))
+    }
+
+    pub fn unvoted_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let unvoted = self.unvoted();
+
+        if unvoted.len() < 2 {
+            return self.min_pair_with(rng);
+        }
+
+        let i1 = rng.gen_range(0..unvoted.len());
+        let i2 = rng.gen_range(0..(unvoted.len() - 1));
+        if i2 >= i1 {
+            Some((unvoted[i1], unvoted[i2 + 1]))
+        } else {
+            Some((unvoted[i1], unvoted[i2]))
+        }
+    }
+
+    pub fn cross_tag_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.cross_tag_pair_with(rng))
+    }
+
+    /// Picks two unlocked entries without any tag in common, every such pair being equally
+    /// likely. The lower index comes first.
+    pub fn cross_tag_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if self.session_complete() {
+            return None;
+        }
+
+        weighted_pair(&self.reduced(), rng, |a, b| {
+            if self[a].shares_tag(&self[b]) {
+                0.0
+            } else {
+                1.0
+            }
+        })
+    }
+
+    pub fn min_equal_pair(&mut self) -> Option<(usize, usize)> {
+        self.with_rng(|rv, rng| rv.min_equal_pair_with(rng))
+    }
+
+    pub fn min_equal_pair_with<R: Rng>(&self, rng: &mut R) -> Option<(usize, usize)> {
+        if rng.gen_bool(0.5) {
+            match self.equal_pair_with(rng) {
+                Some((a, b)) => Some((a, b)),
+                None => self.min_pair_with(rng),
+            }
+        } else {
+            self.min_pair_with(rng)
+        }
+    }
+
+    pub fn sample(&mut self, n: usize) -> Vec<usize> {
+        self.with_rng(|rv, rng| rv.sample_with(n, rng))
+    }
+
+    /// Chooses `n` distinct indices (all of them if `n >= len`) with reservoir sampling.
+    /// Unlike the pair strategies this includes locked entries.
+    pub fn sample_with<R: Rng>(&self, n: usize, rng: &mut R) -> Vec<usize> {
+        let mut reservoir: Vec<usize> = (0..n.min(self.len())).collect();
+
+        for i in n..self.len() {
+            let j = rng.gen_range(0..=i);
+
+            if j < n {
+                reservoir[j] = i;
+            }
+        }
+
+        reservoir
+    }
+
+    pub fn random_group(&mut self, k: usize) -> Option<Vec<usize>> {
+        self.with_rng(|rv, rng| rv.random_group_with(k, rng))
+    }
+
+    /// Chooses `k` distinct unlocked entries in random order, `None` if there are fewer.
+    pub fn random_group_with<R: Rng>(&self, k: usize, rng: &mut R) -> Option<Vec<usize>> {
+        if self.session_complete() {
+            return None;
+        }
+
+        let reduced = self.reduced();
+
+        if reduced.len() < k {
+            return None;
+        }
+
+        Some(reduced.choose_multiple(rng, k).copied().collect())
+    }
+}
+
+/// Compares two snapshots of a list, e.g. the files before and after a session.
+///
+/// `added` and `changed` follow the order of `b`, `removed` the order of `a`.
+pub fn diff(a: &RelVec, b: &RelVec) -> RelDiff {
+    let old: HashMap<&str, &RelEntry> = a.iter().map(|e| (e.name.as_str(), e)).collect();
+    let new: HashSet<&str> = b.iter().map(|e| e.name.as_str()).collect();
+    let mut diff = RelDiff {
+        removed: a
+            .iter()
+            .filter(|e| !new.contains(e.name.as_str()))
+            .map(|e| e.name.clone())
+            .collect(),
+        ..Default::default()
+    };
+
+    for e in b.iter() {
+        match old.get(e.name.as_str()) {
+            None => diff.added.push(e.name.clone()),
+            Some(&before) => {
+                let (before, after) = (EntryStats::from(before), EntryStats::from(e));
+
+                if before != after {
+                    diff.changed.push(EntryChange {
+                        name: e.name.clone(),
+                        before,
+                        after,
+                    });
+                }
+            }
+        }
+    }
+
+    diff
+}
+
+/// Share (0 to 1) of the pairs decided in both lists where both picked the same winner.
+///
+/// Entries are matched by name. A pair counts as decided if one of the two has won their
+/// head-to-head record more often than the other, tied or never compared pairs are left out.
+/// Returns `None` if no pair is decided in both lists.
+pub fn pairwise_agreement(a: &RelVec, b: &RelVec) -> Option<f64> {
+    let winner = |rv: &RelVec, x: usize, y: usize| match rv.head_to_head(x, y) {
+        (w, l) if w > l => Some(true),
+        (w, l) if w < l => Some(false),
+        _ => None,
+    };
+    let shared: Vec<(usize, usize)> = a
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| b.position(&e.name).map(|j| (i, j)))
+        .collect();
+    let (mut decided, mut agreed) = (0usize, 0usize);
+
+    for (x, &(a1, b1)) in shared.iter().enumerate() {
+        for &(a2, b2) in &shared[x + 1..] {
+            if let (Some(wa), Some(wb)) = (winner(a, a1, a2), winner(b, b1, b2)) {
+                decided += 1;
+                if wa == wb {
+                    agreed += 1;
+                }
+            }
+        }
+    }
+
+    if decided == 0 {
+        None
+    } else {
+        Some(agreed as f64 / decided as f64)
+    }
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+
+    crc.update(data);
+    crc.sum()
+}
+
+fn skip_key<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn rank_entries(entries: &[RelEntry]) -> Vec<(usize, &RelEntry)> {
+    let mut voted: Vec<&RelEntry> = entries.iter().filter(|e| e.votes > 0).collect();
+    voted.sort_by(|a, b| a.compare_percentage(b).reverse());
+
+    let mut v: Vec<(usize, &RelEntry)> = Vec::with_capacity(entries.len());
+
+    for (i, e) in voted.iter().enumerate() {
+        let rank = match v.last() {
+            Some((rank, prev)) if prev.compare_percentage(e) == Ordering::Equal => *rank,
+            _ => i + 1,
+        };
+        v.push((rank, e));
+    }
+
+    let unranked = v.len() + 1;
+    v.extend(
+        entries
+            .iter()
+            .filter(|e| e.votes == 0)
+            .map(|e| (unranked, e)),
+    );
+
+    v
+}
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+
+    loop {
+        match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (a_digits, a_rest) =
+                    a.split_at(a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len()));
+                let (b_digits, b_rest) =
+                    b.split_at(b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len()));
+                let a_value = a_digits.trim_start_matches('0');
+                let b_value = b_digits.trim_start_matches('0');
+                let ord = a_value
+                    .len()
+                    .cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value))
+                    .then_with(|| a_digits.len().cmp(&b_digits.len()));
+
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                a = a_rest;
+                b = b_rest;
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a = &a[x.len_utf8()..];
+                b = &b[y.len_utf8()..];
+            }
+        }
+    }
+}
+
+/// Picks a pair of `candidates` with a chance proportional to `weight(a, b)` without listing
+/// every pair: the first entry is drawn by the total weight of its pairs and the second among
+/// its partners. The lower index comes first. `None` if no pair has a positive weight.
+fn weighted_pair<R: Rng, F: Fn(usize, usize) -> f64>(
+    candidates: &[usize],
+    rng: &mut R,
+    weight: F,
+) -> Option<(usize, usize)> {
+    let weight = &weight;
+    let row = |a: usize| {
+        candidates
+            .iter()
+            .map(move |&b| if b == a { 0.0 } else { weight(a, b) })
+    };
+    let totals: Vec<f64> = candidates.iter().map(|&a| row(a).sum()).collect();
+
+    let a = candidates[pick_weighted(rng, &totals)?];
+    let b = candidates[pick_weighted(rng, &row(a).collect::<Vec<f64>>())?];
+
+    Some((a.min(b), a.max(b)))
+}
+
+/// Index into `weights` drawn with a chance proportional to the weight.
+fn pick_weighted<R: Rng>(rng: &mut R, weights: &[f64]) -> Option<usize> {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut x = rng.gen::<f64>() * total;
+    for (i, &w) in weights.iter().enumerate() {
+        x -= w;
+        if x < 0.0 && w > 0.0 {
+            return Some(i);
+        }
+    }
+
+    weights.iter().rposition(|&w| w > 0.0)
+}
+
+impl Default for RelVec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compares the entries by name only, see `RelVec::deep_eq`.
+impl PartialEq for RelVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Deref for RelVec {
+    type Target = Vec<RelEntry>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for RelVec {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl AsRef<Vec<RelEntry>> for RelVec {
+    fn as_ref(&self) -> &Vec<RelEntry> {
+        &self.inner
+    }
+}
+
+impl AsMut<Vec<RelEntry>> for RelVec {
+    fn as_mut(&mut self) -> &mut Vec<RelEntry> {
+        &mut self.inner
+    }
+}
+
+impl Index<usize> for RelVec {
+    type Output = RelEntry;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.inner[index]
+    }
+}
+
+impl IndexMut<usize> for RelVec {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.inner[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        cmp::Ordering,
+        collections::HashMap,
+        fmt::Write as _,
+        fs::{self, File},
+        io::{BufWriter, Write},
+        rc::Rc,
+    };
+
+    use rand::{rngs::mock::StepRng, rngs::StdRng, SeedableRng};
+
+    use super::{
+        diff, pairwise_agreement, EntryChange, EntryStats, Matchup, MergeStrategy, RelDiff,
+        RelEntry, RelStats, RelVec, SyncSummary,
+    };
+    use crate::{error::Error, vote::VoteStrategy};
+
+    #[test]
+    fn rel_entry_new() {
+        assert_eq!(
+            RelEntry {
+                name: "abc".to_owned(),
+                wins: 125132,
+                votes: 12551,
+                locked: false,
+                ..Default::default()
+            },
+            RelEntry::new("abc".to_owned(), 125132, 12551)
+        );
+    }
+
+    #[test]
+    fn rel_entry_reset() {
+        let a = RelEntry {
+            name: "abc".to_owned(),
+            wins: 0,
+            votes: 0,
+            locked: false,
+            ..Default::default()
+        };
+        let mut b = RelEntry {
+            name: "abc".to_owned(),
+            wins: 125132,
+            votes: 12551,
+            locked: true,
+            ..Default::default()
+        };
+
+        b.reset();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_entry_percentage() {
+        let mut e = RelEntry::new("abc".to_owned(), 0, 0);
+
+        assert!(e.percentage().is_nan());
+
+        e.votes = 1;
+
+        assert_eq!(e.percentage(), 0.0);
+
+        e.wins = 1;
+
+        assert_eq!(e.percentage(), 100.0);
+
+        e.votes = 2;
+
+        assert_eq!(e.percentage(), 50.0);
+
+        e.wins = 125;
+        e.votes = 312;
+
+        assert_eq!(e.percentage(), 40.06410256410256);
+    }
+
+    #[test]
+    fn rel_entry_compare_percentage() {
+        let mut a = RelEntry::new("abc".to_owned(), 0, 0);
+        let mut b = RelEntry::new("abc".to_owned(), 0, 0);
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+
+        b.votes = 1;
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+
+        a.wins = 1;
+        a.votes = 2;
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Greater);
+
+        b.wins = 1;
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Less);
+
+        b.votes = 2;
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+
+        let a = RelEntry::new("abc".to_owned(), u32::MAX - 1, u32::MAX);
+        let b = RelEntry::new("bcd".to_owned(), 3, 4);
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Greater);
+        assert_eq!(b.compare_percentage(&a), Ordering::Less);
+        assert_eq!(a.compare_percentage(&a.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn rel_entry_absorb_overflow() {
+        let mut a = RelEntry::new("abc".to_owned(), 1, u32::MAX - 1);
+        a.beaten.insert("cde".to_string(), 1);
+        let mut b = RelEntry::new("bcd".to_owned(), 1, 1);
+        b.beaten.insert("cde".to_string(), u32::MAX);
+
+        assert!(matches!(a.absorb(b), Err(Error::Overflow(name)) if name == "abc"));
+        assert_eq!(
+            (a.wins, a.votes, a.wins_against("cde")),
+            (1, u32::MAX - 1, 1)
+        );
+
+        a.absorb(RelEntry::new("bcd".to_owned(), 1, 1)).unwrap();
+
+        assert_eq!((a.wins, a.votes), (2, u32::MAX));
+        assert!(a.absorb(RelEntry::new("bcd".to_owned(), 0, 1)).is_err());
+    }
+
+    #[test]
+    fn rel_entry_partial_eq() {
+        let a = RelEntry {
+            name: "abc".to_owned(),
+            wins: 125132,
+            votes: 1263,
+            locked: false,
+            ..Default::default()
+        };
+        let b = RelEntry {
+            name: "abc".to_owned(),
+            wins: 1251,
+            votes: 1361621,
+            locked: false,
+            ..Default::default()
+        };
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_entry_to_string() {
+        let a = RelEntry {
+            name: "abc".to_owned(),
+            wins: 12,
+            votes: 36,
+            locked: false,
+            ..Default::default()
+        };
+
+        assert_eq!(a.to_string(), "abc - 12/36 - 33.3%");
+    }
+
+    #[test]
+    fn rel_entry_from_string() {
+        let a = RelEntry {
+            name: "abc".to_owned(),
+            wins: 0,
+            votes: 0,
+            locked: false,
+            ..Default::default()
+        };
+        let b = "abc".to_owned().into();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_vec_new() {
+        let a = RelVec {
+            inner: Vec::new(),
+            ..RelVec::new()
+        };
+        let b = RelVec::new();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_vec_create() {
+        let a = RelVec {
+            inner: Vec::from([
+                RelEntry::new("abc".to_string(), 1251, 16162),
+                RelEntry::new("adsga".to_string(), 1251, 1236),
+            ]),
+            ..RelVec::new()
+        };
+        let b = RelVec::create(Vec::from(["abc".to_string(), "adsga".to_string()]));
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_vec_from() {
+        let file = File::create("_rel_vec_from.txt").unwrap();
+        let mut writer = BufWriter::new(file);
+
+        writer.write_all(b"a\nb\nc\n").unwrap();
+
+        drop(writer);
+
+        let a = RelVec::create(["a".to_string(), "b".to_string(), "c".to_string()].to_vec());
+        let b = RelVec::from("_rel_vec_from.txt").unwrap();
+
+        fs::remove_file("_rel_vec_from.txt").unwrap();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_vec_load() {
+        let file = File::create("_rel_vec_load.txt").unwrap();
+        let mut writer = BufWriter::new(file);
+
+        writer
+            .write_all(b"[{\"n\":\"abc\",\"w\":2,\"v\":3}]")
+            .unwrap();
+
+        drop(writer);
+
+        let a = RelVec {
+            inner: [RelEntry::new("abc".to_owned(), 2, 3)].to_vec(),
+            ..RelVec::new()
+        };
+        let b = RelVec::load("_rel_vec_load.txt").unwrap();
+
+        fs::remove_file("_rel_vec_load.txt").unwrap();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rel_vec_save() {
+        let rv = RelVec::create(["abc".to_string()].to_vec());
+        rv.save("_rel_vec_save.txt").unwrap();
+
+        let a = b"[{\"n\":\"abc\",\"w\":0,\"v\":0,\"l\":false}]";
+        let b = fs::read("_rel_vec_save.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save.txt").unwrap();
+
+        assert_eq!(a, b.as_slice());
+    }
+
+    #[test]
+    fn rel_vec_add() {
+        let mut rv = RelVec {
+            inner: Vec::new(),
+            ..RelVec::new()
+        };
+
+        rv.add("abc".to_owned());
+
+        assert_eq!(
+            rv,
+            RelVec {
+                inner: [RelEntry::new("abc".to_owned(), 0, 0)].to_vec(),
+                ..RelVec::new()
+            }
+        )
+    }
+
+    #[test]
+    fn rel_vec_remove() {
+        let mut rv = RelVec {
+            inner: [RelEntry::new("abc".to_owned(), 0, 0)].to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.remove(|entry| entry.name.len() == 3);
+
+        assert_eq!(rv, RelVec::new());
+    }
+
+    #[test]
+    fn rel_vec_sort_percentage() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("bec".to_owned(), 1, 1),
+                RelEntry::new("ads".to_owned(), 1, 2),
+                RelEntry::new("foo".to_owned(), 3, 4),
+                RelEntry::new("bar".to_owned(), 1, 4),
+                RelEntry::new("abc".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.sort_percentage();
+
+        assert_eq!(
+            rv,
+            RelVec {
+                inner: [
+                    RelEntry::new("bec".to_owned(), 1, 1),
+                    RelEntry::new("foo".to_owned(), 3, 4),
+                    RelEntry::new("ads".to_owned(), 1, 2),
+                    RelEntry::new("bar".to_owned(), 1, 4),
+                    RelEntry::new("abc".to_owned(), 0, 0),
+                ]
+                .to_vec(),
+                ..RelVec::new()
+            }
+        );
+    }
+
+    #[test]
+    fn rel_vec_min_votes() {
+        let rv = RelVec {
+            inner: [
+                RelEntry {
+                    name: "abc".to_string(),
+                    wins: 12,
+                    votes: 123,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "bcd".to_string(),
+                    wins: 125,
+                    votes: 123,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 0,
+                    votes: 0,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "cde".to_string(),
+                    wins: 12,
+                    votes: 12632,
+                    locked: false,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.min_votes(), [0, 1].to_vec());
+    }
+
+    #[test]
+    fn rel_vec_random_pair() {
+        for _ in 0..5 {
+            let mut rv = RelVec {
+                inner: [
+                    RelEntry {
+                        name: "abc".to_string(),
+                        wins: 0,
+                        votes: 0,
+                        locked: false,
+                        ..Default::default()
+                    },
+                    RelEntry {
+                        name: "locked".to_string(),
+                        wins: 0,
+                        votes: 0,
+                        locked: true,
+                        ..Default::default()
+                    },
+                    RelEntry {
+                        name: "def".to_string(),
+                        wins: 0,
+                        votes: 0,
+                        locked: false,
+                        ..Default::default()
+                    },
+                ]
+                .to_vec(),
+                ..RelVec::new()
+            };
+
+            let (a, b) = rv.random_pair().unwrap();
+
+            assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+        }
+    }
+
+    #[test]
+    fn rel_vec_min_pair() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry {
+                    name: "abc".to_string(),
+                    wins: 0,
+                    votes: 2,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 0,
+                    votes: 0,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "def".to_string(),
+                    wins: 0,
+                    votes: 1,
+                    locked: false,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let (a, b) = rv.min_pair().unwrap();
+
+        assert_eq!((a, b), (2, 0))
+    }
+
+    #[test]
+    fn rel_vec_equal_pair() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry {
+                    name: "abc".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 1,
+                    votes: 1,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "def".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: false,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let (a, b) = rv.equal_pair().unwrap();
+
+        assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+    }
+
+    #[test]
+    fn rel_vec_equal_pair_fail() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry {
+                    name: "abc".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 1,
+                    votes: 1,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "def".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: true,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.equal_pair(), None);
+    }
+
+    #[test]
+    fn rel_vec_nearest_pair() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry {
+                    name: "abc".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 1,
+                    votes: 1,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "def".to_string(),
+                    wins: 5,
+                    votes: 8,
+                    locked: false,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let (a, b) = rv.nearest_pair().unwrap();
+
+        assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+    }
+
+    #[test]
+    fn rel_vec_nearest_pair_two() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry {
+                    name: "abc".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 1,
+                    votes: 1,
+                    locked: false,
+                    ..Default::default()
+                },
+                RelEntry {
+                    name: "def".to_string(),
+                    wins: 5,
+                    votes: 8,
+                    locked: true,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        let (a, b) = rv.nearest_pair().unwrap();
+
+        assert!((a, b) == (0, 1) || (a, b) == (1, 0));
+    }
+
+    #[test]
+    fn rel_vec_unvoted() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 2),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 0,
+                    votes: 0,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("cde".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.unvoted(), [0, 3].to_vec());
+    }
+
+    #[test]
+    fn rel_vec_unvoted_pair() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 2),
+                RelEntry::new("cde".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let (a, b) = rv.unvoted_pair().unwrap();
+
+        assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+    }
+
+    #[test]
+    fn rel_vec_position_ci() {
+        let rv = RelVec::create(["Alien".to_string(), "Brazil".to_string()].to_vec());
+
+        assert_eq!(rv.position_ci("brazil"), Some(1));
+        assert_eq!(rv.position_ci("ALIEN"), Some(0));
+        assert_eq!(rv.position_ci("Aliens"), None);
+    }
+
+    #[test]
+    fn rel_vec_dedup_ci() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("Alien".to_owned(), 1, 2),
+                RelEntry::new("Brazil".to_owned(), 0, 1),
+                RelEntry {
+                    name: "alien".to_string(),
+                    wins: 2,
+                    votes: 3,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("Äpfel".to_owned(), 0, 0),
+                RelEntry::new("äpfel".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.dedup_ci().unwrap();
+
+        assert_eq!(rv.len(), 4);
+        assert_eq!(rv[0].name, "Alien");
+        assert_eq!((rv[0].wins, rv[0].votes, rv[0].locked), (3, 5, true));
+        assert_eq!(rv[1].name, "Brazil");
+        assert_eq!(rv[2].name, "Äpfel");
+        assert_eq!(rv[3].name, "äpfel");
+
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, u32::MAX),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry::new("ABC".to_owned(), 0, 1),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        let before = rv.clone();
+
+        assert!(matches!(rv.dedup_ci(), Err(Error::Overflow(name)) if name == "abc"));
+        assert!(rv.deep_eq(&before));
+    }
+
+    #[test]
+    fn rel_vec_histogram() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 0, 3),
+                RelEntry::new("cde".to_owned(), 1, 2),
+                RelEntry::new("def".to_owned(), 2, 2),
+                RelEntry::new("efg".to_owned(), 49, 100),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.histogram(2), [2, 2].to_vec());
+        assert_eq!(rv.histogram(4), [1, 1, 1, 1].to_vec());
+        assert_eq!(rv.histogram(1), [4].to_vec());
+        assert_eq!(rv.histogram(0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn rel_vec_save_pretty() {
+        let rv = RelVec::create(["abc".to_string()].to_vec());
+        rv.save_pretty("_rel_vec_save_pretty.txt").unwrap();
+
+        let a = fs::read_to_string("_rel_vec_save_pretty.txt").unwrap();
+        let b = RelVec::load("_rel_vec_save_pretty.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save_pretty.txt").unwrap();
+
+        assert!(a.contains("\n  {\n    \"n\": \"abc\","));
+        assert_eq!(rv, b);
+    }
+
+    #[test]
+    fn rel_vec_vote() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        rv.vote(1, 0).unwrap();
+        rv.vote(1, 0).unwrap();
+        rv.vote(0, 1).unwrap();
+
+        assert_eq!((rv[0].wins, rv[0].votes), (1, 3));
+        assert_eq!((rv[1].wins, rv[1].votes), (2, 3));
+        assert_eq!(rv.head_to_head(0, 1), (1, 2));
+        assert_eq!(rv.head_to_head(1, 0), (2, 1));
+    }
+
+    #[test]
+    fn rel_vec_remaining_pairs() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 0,
+                    votes: 0,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(
+            rv.remaining_pairs(),
+            [(0, 1), (0, 3), (0, 4), (1, 3), (1, 4), (3, 4)].to_vec()
+        );
+
+        rv.vote(3, 0).unwrap();
+        rv.vote(1, 4).unwrap();
+
+        assert_eq!(
+            rv.remaining_pairs(),
+            [(0, 1), (0, 4), (1, 3), (3, 4)].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_load_head_to_head() {
+        let file = File::create("_rel_vec_load_head_to_head.txt").unwrap();
+        let mut writer = BufWriter::new(file);
+
+        writer
+            .write_all(b"[{\"n\":\"abc\",\"w\":2,\"v\":3,\"h\":{\"bcd\":2}},{\"n\":\"bcd\",\"w\":1,\"v\":3}]")
+            .unwrap();
+
+        drop(writer);
+
+        let rv = RelVec::load("_rel_vec_load_head_to_head.txt").unwrap();
+
+        fs::remove_file("_rel_vec_load_head_to_head.txt").unwrap();
+
+        assert_eq!(rv.head_to_head(0, 1), (2, 0));
+    }
+
+    #[test]
+    fn rel_vec_session_limit() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        assert!(!rv.session_complete());
+        assert_eq!(rv.remaining_comparisons(), None);
+
+        rv.session_limit = Some(2);
+        rv.vote(0, 1).unwrap();
+
+        assert_eq!(rv.comparisons_done(), 1);
+        assert_eq!(rv.remaining_comparisons(), Some(1));
+        assert!(rv.random_pair().is_some());
+
+        rv.vote(1, 0).unwrap();
+
+        assert!(rv.session_complete());
+        assert_eq!(rv.remaining_comparisons(), Some(0));
+        assert_eq!(rv.random_pair(), None);
+        assert_eq!(rv.min_pair(), None);
+        assert_eq!(rv.equal_pair(), None);
+        assert_eq!(rv.nearest_pair(), None);
+        assert_eq!(rv.unvoted_pair(), None);
+        assert_eq!(rv.min_equal_pair(), None);
+    }
+
+    #[test]
+    fn rel_vec_load_names_json() {
+        let file = File::create("_rel_vec_load_names_json.txt").unwrap();
+        let mut writer = BufWriter::new(file);
+
+        writer.write_all(b"[\"a\",\"b\",\"c\"]").unwrap();
+
+        drop(writer);
+
+        let a = RelVec::create(["a".to_string(), "b".to_string(), "c".to_string()].to_vec());
+        let b = RelVec::load_names_json("_rel_vec_load_names_json.txt").unwrap();
+
+        fs::remove_file("_rel_vec_load_names_json.txt").unwrap();
+
+        assert_eq!(a, b);
+        assert!(b.iter().all(|e| e.wins == 0 && e.votes == 0 && !e.locked));
+    }
+
+    #[test]
+    fn rel_vec_keep() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("bcd".to_owned(), 3, 4),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 2, 2),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.keep(|e| e.percentage() > 50.0);
+
+        assert_eq!(
+            rv,
+            RelVec::create(["bcd".to_string(), "def".to_string()].to_vec())
+        );
+    }
+
+    #[test]
+    fn rel_vec_condorcet_winner() {
+        let mut rv =
+            RelVec::create(["abc".to_string(), "bcd".to_string(), "cde".to_string()].to_vec());
+
+        assert_eq!(rv.condorcet_winner(), None);
+
+        rv.vote(1, 0).unwrap();
+        rv.vote(1, 2).unwrap();
+        rv.vote(2, 1).unwrap();
+        rv.vote(1, 2).unwrap();
+        rv.vote(0, 2).unwrap();
+
+        assert_eq!(rv.condorcet_winner(), Some(1));
+    }
+
+    #[test]
+    fn rel_vec_condorcet_winner_cycle() {
+        let mut rv = RelVec::create(
+            [
+                "rock".to_string(),
+                "paper".to_string(),
+                "scissors".to_string(),
+            ]
+            .to_vec(),
+        );
+
+        rv.vote(1, 0).unwrap();
+        rv.vote(2, 1).unwrap();
+        rv.vote(0, 2).unwrap();
+
+        assert_eq!(rv.condorcet_winner(), None);
+    }
+
+    #[test]
+    fn rel_vec_random_pair_with() {
+        let rv = RelVec::create(["abc".to_string(), "bcd".to_string(), "cde".to_string()].to_vec());
+
+        assert_eq!(rv.random_pair_with(&mut StepRng::new(0, 0)), Some((0, 1)));
+    }
+
+    #[test]
+    fn rel_vec_pair_with_seeded() {
+        let rv = RelVec::create(
+            ["a", "b", "c", "d", "e", "f"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        );
+        let mut a = StdRng::seed_from_u64(42);
+        let mut b = StdRng::seed_from_u64(42);
+
+        for _ in 0..10 {
+            assert_eq!(rv.random_pair_with(&mut a), rv.random_pair_with(&mut b));
+            assert_eq!(rv.nearest_pair_with(&mut a), rv.nearest_pair_with(&mut b));
+            assert_eq!(
+                rv.min_equal_pair_with(&mut a),
+                rv.min_equal_pair_with(&mut b)
+            );
+        }
+    }
+
+    #[test]
+    fn rel_vec_save_gz() {
+        let mut rv = RelVec::create((0..1000).map(|i| format!("entry {}", i)).collect());
+        rv.vote(3, 7).unwrap();
+        rv[12].locked = true;
+
+        rv.save("_rel_vec_save_gz.txt").unwrap();
+        rv.save_gz("_rel_vec_save_gz.txt.gz").unwrap();
+
+        let plain = fs::metadata("_rel_vec_save_gz.txt").unwrap().len();
+        let compressed = fs::metadata("_rel_vec_save_gz.txt.gz").unwrap().len();
+        let b = RelVec::load_gz("_rel_vec_save_gz.txt.gz").unwrap();
+
+        fs::remove_file("_rel_vec_save_gz.txt").unwrap();
+        fs::remove_file("_rel_vec_save_gz.txt.gz").unwrap();
+
+        assert_eq!(rv, b);
+        assert_eq!(b.head_to_head(3, 7), (1, 0));
+        assert!(b[12].locked);
+        assert!(compressed * 4 < plain);
+    }
+
+    #[test]
+    fn rel_vec_percentile() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("bcd".to_owned(), 2, 4),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 3, 4),
+                RelEntry::new("efg".to_owned(), 4, 4),
+                RelEntry::new("fgh".to_owned(), 1, 2),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.percentile(0), 0.0);
+        assert_eq!(rv.percentile(1), 25.0);
+        assert_eq!(rv.percentile(2), 0.0);
+        assert_eq!(rv.percentile(3), 75.0);
+        assert_eq!(rv.percentile(4), 100.0);
+        assert_eq!(rv.percentile(5), 25.0);
+    }
+
+    #[test]
+    fn rel_entry_is_valid() {
+        assert!(RelEntry::new("abc".to_owned(), 0, 0).is_valid());
+        assert!(RelEntry::new("abc".to_owned(), 3, 3).is_valid());
+        assert!(!RelEntry::new("abc".to_owned(), 4, 3).is_valid());
+    }
+
+    #[test]
+    fn rel_vec_validate() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        assert!(rv.validate().is_ok());
+
+        rv[1].wins = 2;
+
+        assert!(matches!(rv.validate(), Err(Error::InvalidEntry(name)) if name == "bcd"));
+    }
+
+    #[test]
+    fn rel_vec_load_validated() {
+        fs::write(
+            "_rel_vec_load_validated.txt",
+            b"[{\"n\":\"abc\",\"w\":2,\"v\":3},{\"n\":\"bcd\",\"w\":4,\"v\":3}]",
+        )
+        .unwrap();
+
+        let a = RelVec::load("_rel_vec_load_validated.txt");
+        let b = RelVec::load_validated("_rel_vec_load_validated.txt");
+
+        fs::remove_file("_rel_vec_load_validated.txt").unwrap();
+
+        assert!(a.is_ok());
+        assert!(matches!(b, Err(Error::InvalidEntry(name)) if name == "bcd"));
+    }
+
+    #[test]
+    fn rel_vec_random_pair_weight() {
+        let mut rv =
+            RelVec::create(["abc".to_string(), "bcd".to_string(), "cde".to_string()].to_vec());
+        rv[1].weight = 0.0;
+
+        for _ in 0..20 {
+            let (a, b) = rv.random_pair().unwrap();
+
+            assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+        }
+
+        rv[0].weight = 0.0;
+
+        assert_eq!(rv.random_pair(), None);
+    }
+
+    #[test]
+    fn rel_vec_pair_strategies_weight() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def", "efg"]);
+        for i in 0..5 {
+            rv.vote(i, (i + 1) % 5).unwrap();
+        }
+        rv[1].weight = 0.0;
+        rv[2].weight = f64::NAN;
+        rv[3].weight = -1.0;
+
+        assert_eq!(rv.reduced(), [0, 4].to_vec());
+        assert_eq!(rv.active_len(), 2);
+        assert_eq!(rv.round_robin(), [(0, 4)].to_vec());
+
+        for _ in 0..20 {
+            for (a, b) in [
+                rv.random_pair().unwrap(),
+                rv.min_pair().unwrap(),
+                rv.equal_pair().unwrap(),
+                rv.nearest_pair().unwrap(),
+                rv.informative_pair().unwrap(),
+                rv.min_equal_pair().unwrap(),
+            ] {
+                assert!((a, b) == (0, 4) || (a, b) == (4, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn rel_vec_random_pair_weight_distribution() {
+        let mut rv =
+            RelVec::create(["abc".to_string(), "bcd".to_string(), "cde".to_string()].to_vec());
+        rv[0].weight = 8.0;
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut with_heavy = 0;
+
+        for _ in 0..1000 {
+            let (a, b) = rv.random_pair_with(&mut rng).unwrap();
+            if a == 0 || b == 0 {
+                with_heavy += 1;
+            }
+        }
+
+        // 8/10 + 2 * 1/10 * 8/9 = 0.978 of all pairs contain the heavy entry
+        assert!(with_heavy > 940);
+    }
+
+    #[test]
+    fn rel_vec_load_weight() {
+        fs::write(
+            "_rel_vec_load_weight.txt",
+            b"[{\"n\":\"abc\",\"w\":2,\"v\":3},{\"n\":\"bcd\",\"g\":2.5}]",
+        )
+        .unwrap();
+
+        let mut rv = RelVec::load("_rel_vec_load_weight.txt").unwrap();
+
+        assert_eq!(rv[0].weight, 1.0);
+        assert_eq!(rv[1].weight, 2.5);
+
+        rv[0].weight = 0.5;
+        rv.save("_rel_vec_load_weight.txt").unwrap();
+
+        let b = fs::read("_rel_vec_load_weight.txt").unwrap();
+
+        fs::remove_file("_rel_vec_load_weight.txt").unwrap();
+
+        assert_eq!(
+            b"[{\"n\":\"abc\",\"w\":2,\"v\":3,\"l\":false,\"g\":0.5},{\"n\":\"bcd\",\"w\":0,\"v\":0,\"l\":false,\"g\":2.5}]",
+            b.as_slice()
+        );
+    }
+
+    #[test]
+    fn rel_vec_sort_name() {
+        let mut rv = RelVec::create(
+            ["b", "a10", "B", "a2", "A1"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        );
+
+        rv.sort_name();
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["A1", "B", "a10", "a2", "b"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_sort_name_ci() {
+        let mut rv = RelVec::create(
+            ["b", "a10", "B", "a2", "A1"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        );
+
+        rv.sort_name_ci();
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["A1", "a10", "a2", "b", "B"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_sort_name_natural() {
+        let mut rv = RelVec::create(
+            [
+                "Episode 10",
+                "Episode 2",
+                "Episode 1",
+                "Episode 02",
+                "Episode 100000000000000000000000",
+                "Episode 9b",
+                "Episode 9a",
+                "Episode",
+                "Episode 001",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        );
+
+        rv.sort_name_natural();
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            [
+                "Episode",
+                "Episode 1",
+                "Episode 001",
+                "Episode 2",
+                "Episode 02",
+                "Episode 9a",
+                "Episode 9b",
+                "Episode 10",
+                "Episode 100000000000000000000000",
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_enable_autosave() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        rv.enable_autosave("_rel_vec_enable_autosave.txt", 2);
+        rv.vote(0, 1).unwrap();
+
+        assert!(fs::metadata("_rel_vec_enable_autosave.txt").is_err());
+
+        rv.vote(0, 1).unwrap();
+
+        let b = RelVec::load("_rel_vec_enable_autosave.txt").unwrap();
+
+        fs::remove_file("_rel_vec_enable_autosave.txt").unwrap();
+
+        assert_eq!((b[0].wins, b[0].votes), (2, 2));
+
+        rv.enable_autosave("_rel_vec_enable_autosave/missing/dir.txt", 1);
+
+        assert!(matches!(rv.vote(1, 0), Err(Error::IoError(_))));
+        assert_eq!(rv[1].wins, 1);
+    }
+
+    #[test]
+    fn rel_vec_ranked() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 1, 2),
+                RelEntry::new("efg".to_owned(), 2, 4),
+                RelEntry::new("fgh".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(
+            rv.ranked()
+                .into_iter()
+                .map(|(rank, e)| (rank, e.name.as_str()))
+                .collect::<Vec<(usize, &str)>>(),
+            [
+                (1, "cde"),
+                (2, "def"),
+                (2, "efg"),
+                (4, "abc"),
+                (5, "bcd"),
+                (5, "fgh"),
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_cross_tag_pair() {
+        let mut rv =
+            RelVec::create(["abc".to_string(), "bcd".to_string(), "cde".to_string()].to_vec());
+        rv[0].tags = ["movie".to_string(), "scifi".to_string()].to_vec();
+        rv[1].tags = ["movie".to_string()].to_vec();
+        rv[2].tags = ["book".to_string(), "scifi".to_string()].to_vec();
+
+        assert_eq!(rv.cross_tag_pair(), Some((1, 2)));
+
+        rv[2].tags.push("movie".to_string());
+
+        assert_eq!(rv.cross_tag_pair(), None);
+
+        rv.add("def".to_string());
+        rv[3].tags = ["book".to_string()].to_vec();
+        rv[1].locked = true;
+
+        assert_eq!(rv.cross_tag_pair(), Some((0, 3)));
+
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+        let mut seen = HashMap::new();
+        for _ in 0..600 {
+            *seen.entry(rv.cross_tag_pair().unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(seen.len(), 6);
+        assert!(seen.values().all(|&c| c > 50));
+    }
+
+    #[test]
+    fn rel_vec_get() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        assert_eq!(rv.get(1).map(|e| e.name.as_str()), Some("bcd"));
+        assert_eq!(rv.get(2), None);
+
+        rv.get_mut(0).unwrap().wins = 3;
+
+        assert_eq!(rv[0].wins, 3);
+        assert!(rv.get_mut(2).is_none());
+    }
+
+    #[test]
+    fn rel_vec_get_by_name() {
+        let rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        assert_eq!(rv.get_by_name("bcd").map(|e| e.name.as_str()), Some("bcd"));
+        assert_eq!(rv.get_by_name("BCD"), None);
+    }
+
+    #[test]
+    fn rel_entry_std_error() {
+        assert_eq!(RelEntry::new("abc".to_owned(), 0, 0).std_error(), None);
+        assert_eq!(RelEntry::new("abc".to_owned(), 4, 4).std_error(), Some(0.0));
+
+        let a = RelEntry::new("abc".to_owned(), 1, 2).std_error().unwrap();
+        let b = RelEntry::new("abc".to_owned(), 3, 4).std_error().unwrap();
+
+        assert!((a - 0.353_553_390_593_273_8).abs() < 1e-12);
+        assert!((b - 0.216_506_350_946_109_66).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rel_vec_sort_by_lower_ci() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 1),
+                RelEntry::new("cde".to_owned(), 40, 50),
+                RelEntry::new("def".to_owned(), 3, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.sort_by_lower_ci(1.96);
+
+        // 1/1 has a standard error of 0, its lower bound stays at 1
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["bcd", "cde", "def", "abc"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_truncate_to_top() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("bcd".to_owned(), 4, 4),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 0,
+                    votes: 4,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 2, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let removed = rv.truncate_to_top(2);
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["bcd", "cde", "locked"].to_vec()
+        );
+        assert_eq!(
+            removed
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<&str>>(),
+            ["def", "abc"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_names_only() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+        rv.vote(0, 1).unwrap();
+        rv[1].locked = true;
+
+        let b = rv.names_only();
+
+        assert_eq!(rv, b);
+        assert!(b
+            .iter()
+            .all(|e| e.wins == 0 && e.votes == 0 && !e.locked && e.beaten.is_empty()));
+        assert_eq!(rv[0].wins, 1);
+    }
+
+    #[test]
+    fn rel_vec_into_names() {
+        let rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        assert_eq!(
+            rv.into_names(),
+            ["abc".to_string(), "bcd".to_string()].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_apply_decay() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 10, 20),
+                RelEntry::new("bcd".to_owned(), 3, 3),
+                RelEntry::new("cde".to_owned(), 1, 3),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.apply_decay(0.5);
+
+        assert_eq!((rv[0].wins, rv[0].votes), (5, 10));
+        assert_eq!((rv[1].wins, rv[1].votes), (2, 2));
+        assert_eq!((rv[2].wins, rv[2].votes), (1, 2));
+
+        rv.apply_decay(0.0);
+
+        assert!(rv.iter().all(|e| e.wins == 0 && e.votes == 0));
+    }
+
+    #[test]
+    fn rel_vec_is_complete() {
+        let mut rv =
+            RelVec::create(["abc".to_string(), "bcd".to_string(), "cde".to_string()].to_vec());
+
+        assert!(rv.is_complete(0));
+        assert!(!rv.is_complete(1));
+
+        rv.vote(0, 1).unwrap();
+        rv.vote(2, 0).unwrap();
+
+        assert!(!rv.is_complete(1));
+
+        rv.vote(1, 2).unwrap();
+
+        assert!(rv.is_complete(1));
+        assert!(!rv.is_complete(2));
+
+        rv.vote(1, 0).unwrap();
+        rv.vote(0, 2).unwrap();
+        rv.vote(1, 2).unwrap();
+
+        assert!(rv.is_complete(2));
+
+        rv.add("def".to_string());
+
+        assert!(!rv.is_complete(1));
+
+        rv[3].locked = true;
+
+        assert!(rv.is_complete(2));
+    }
+
+    #[test]
+    fn rel_vec_is_complete_trivial() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+
+        assert!(RelVec::new().is_complete(3));
+
+        rv[0].locked = true;
+
+        assert!(rv.is_complete(3));
+    }
+
+    #[test]
+    fn rel_vec_from_strs() {
+        let a = RelVec::create(["abc".to_string(), "adsga".to_string()].to_vec());
+        let b = RelVec::from_strs(&["abc", "adsga"]);
+
+        assert_eq!(a, b);
+        assert!(b.iter().all(|e| e.wins == 0 && e.votes == 0 && !e.locked));
+    }
+
+    #[test]
+    fn rel_vec_on_vote() {
+        let mut rv = RelVec::create(["abc".to_string(), "bcd".to_string()].to_vec());
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let e = Rc::clone(&events);
+
+        rv.on_vote(move |winner, loser| e.borrow_mut().push((winner, loser)));
+        rv.vote(0, 1).unwrap();
+        rv.vote(1, 0).unwrap();
+
+        let mut b = rv.clone();
+        b.vote(0, 1).unwrap();
+
+        rv.clear_on_vote();
+        rv.vote(0, 1).unwrap();
+
+        assert_eq!(*events.borrow(), [(0, 1), (1, 0)].to_vec());
+    }
+
+    #[test]
+    fn rel_vec_sort_by_score() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 2),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry::new("cde".to_owned(), 3, 30),
+                RelEntry::new("def".to_owned(), 9, 10),
+                RelEntry::new("efg".to_owned(), 1, 1),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.sort_by_score(|e| {
+            if e.votes == 0 {
+                f64::NAN
+            } else {
+                f64::from(e.votes)
+            }
+        });
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["cde", "def", "abc", "efg", "bcd"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_cycles_dag() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+
+        rv.vote(0, 1).unwrap();
+        rv.vote(1, 2).unwrap();
+        rv.vote(0, 2).unwrap();
+        rv.vote(3, 2).unwrap();
+        rv.vote(2, 3).unwrap();
+
+        assert!(!rv.has_cycle());
+        assert_eq!(rv.cycles(), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn rel_vec_cycles() {
+        let mut rv = RelVec::from_strs(&["def", "rock", "paper", "scissors"]);
+
+        rv.vote(2, 1).unwrap();
+        rv.vote(3, 2).unwrap();
+        rv.vote(1, 3).unwrap();
+        rv.vote(1, 3).unwrap();
+        rv.vote(3, 1).unwrap();
+        rv.vote(1, 0).unwrap();
+        rv.vote(2, 0).unwrap();
+
+        assert!(rv.has_cycle());
+        assert_eq!(rv.cycles(), [[1, 2, 3].to_vec()].to_vec());
+    }
+
+    #[test]
+    fn rel_vec_seed() {
+        let mut a = RelVec::from_strs(&["a", "b", "c", "d", "e", "f"]);
+        let mut b = a.clone();
+        a.seed(42);
+        b.seed(42);
+
+        let pairs: Vec<Option<(usize, usize)>> = (0..10).map(|_| a.random_pair()).collect();
+
+        assert_eq!(pairs, (0..10).map(|_| b.random_pair()).collect::<Vec<_>>());
+        assert!(pairs.iter().any(|p| *p != pairs[0]));
+    }
+
+    #[test]
+    fn rel_vec_save_rng_state() {
+        let mut rv = RelVec::from_strs(&["a", "b", "c", "d", "e", "f"]);
+        rv.seed(7);
+        rv.random_pair();
+        rv.nearest_pair();
+
+        rv.save("_rel_vec_save_rng_state.txt").unwrap();
+
+        let mut b = RelVec::load("_rel_vec_save_rng_state.txt").unwrap();
+        let content = fs::read_to_string("_rel_vec_save_rng_state.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save_rng_state.txt").unwrap();
+
+        assert!(content.contains("\"rng_state\":{\"seed\":7,"));
+        assert_eq!(rv, b);
+        for _ in 0..10 {
+            assert_eq!(rv.random_pair(), b.random_pair());
+            assert_eq!(rv.equal_pair(), b.equal_pair());
+        }
+    }
+
+    #[test]
+    fn rel_vec_load_without_rng_state() {
+        fs::write(
+            "_rel_vec_load_without_rng_state.txt",
+            b"{\"entries\":[{\"n\":\"abc\",\"w\":2,\"v\":3}]}",
+        )
+        .unwrap();
+
+        let rv = RelVec::load("_rel_vec_load_without_rng_state.txt").unwrap();
+
+        fs::remove_file("_rel_vec_load_without_rng_state.txt").unwrap();
+
+        assert_eq!(rv, RelVec::from_strs(&["abc"]));
+        assert_eq!(rv.rng.state(), None);
+    }
+
+    #[test]
+    fn rel_vec_partition() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("bcd".to_owned(), 3, 4),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 4,
+                    votes: 5,
+                    locked: true,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let (a, b) = rv.partition(|e| e.percentage() >= 50.0);
+
+        assert_eq!(a, RelVec::from_strs(&["bcd", "locked"]));
+        assert_eq!(b, RelVec::from_strs(&["abc", "cde"]));
+        assert_eq!((a[1].wins, a[1].votes, a[1].locked), (4, 5, true));
+        assert_eq!((b[0].wins, b[0].votes), (1, 4));
+    }
+
+    #[test]
+    fn rel_vec_rank_changed_since() {
+        let previous = [
+            RelEntry::new("abc".to_owned(), 3, 4),
+            RelEntry::new("bcd".to_owned(), 2, 4),
+            RelEntry::new("cde".to_owned(), 1, 4),
+            RelEntry::new("def".to_owned(), 0, 4),
+        ];
+        let mut rv = RelVec {
+            inner: previous.to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.rank_changed_since(&previous), 0);
+
+        rv.reverse();
+
+        assert_eq!(rv.rank_changed_since(&previous), 0);
+
+        rv.vote(0, 1).unwrap();
+        rv.vote(0, 1).unwrap();
+
+        // def: 2/6 passes cde: 1/6, abc and bcd keep their ranks
+        assert_eq!(rv.rank_changed_since(&previous), 2);
+
+        rv.add("efg".to_owned());
+        rv.remove(|e| e.name == "abc");
+
+        // bcd and def move up, efg is new and abc is gone, cde is back at 3
+        assert_eq!(rv.rank_changed_since(&previous), 4);
+    }
+
+    #[test]
+    fn rel_vec_from_tsv() {
+        fs::write("_rel_vec_from_tsv.txt", b"abc\t2\t3\nbcd\n\ncde\t0\t1\n").unwrap();
+
+        let rv = RelVec::from_tsv("_rel_vec_from_tsv.txt").unwrap();
+
+        fs::remove_file("_rel_vec_from_tsv.txt").unwrap();
+
+        assert_eq!(rv, RelVec::from_strs(&["abc", "bcd", "cde"]));
+        assert_eq!((rv[0].wins, rv[0].votes), (2, 3));
+        assert_eq!((rv[1].wins, rv[1].votes), (0, 0));
+        assert_eq!((rv[2].wins, rv[2].votes), (0, 1));
+    }
+
+    #[test]
+    fn rel_vec_from_tsv_malformed() {
+        fs::write("_rel_vec_from_tsv_malformed.txt", b"abc\t2\t3\nbcd\tx\t1\n").unwrap();
+        fs::write("_rel_vec_from_tsv_fields.txt", b"abc\nbcd\t1\n").unwrap();
+
+        let a = RelVec::from_tsv("_rel_vec_from_tsv_malformed.txt");
+        let b = RelVec::from_tsv("_rel_vec_from_tsv_fields.txt");
+
+        fs::remove_file("_rel_vec_from_tsv_malformed.txt").unwrap();
+        fs::remove_file("_rel_vec_from_tsv_fields.txt").unwrap();
+
+        assert!(matches!(a, Err(Error::Tsv(2, _))));
+        assert!(matches!(b, Err(Error::Tsv(2, _))));
+    }
+
+    #[test]
+    fn rel_entry_display() {
+        let a = RelEntry::new("abc".to_owned(), 12, 36);
+        let mut b = RelEntry::new("bcd".to_owned(), 0, 0);
+        b.locked = true;
+        let mut buf = String::new();
+
+        write!(buf, "{}\n{}", a, b).unwrap();
+
+        assert_eq!(buf, "abc - 12/36 - 33.3%\nbcd - 0/0 - — [L]");
+        assert_eq!(
+            a.to_string(),
+            format!(
+                "{} - {}/{} - {}{}",
+                a.name,
+                a.wins,
+                a.votes,
+                a.display_percentage(),
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn rel_entry_format_with() {
+        let mut a = RelEntry::new("abc".to_owned(), 1, 4);
+
+        assert_eq!(a.format_with(";", "*"), "abc;1/4;25.0%");
+        assert_eq!(a.format_with(" - ", " [L]"), a.to_string());
+
+        a.locked = true;
+
+        assert_eq!(a.format_with(", ", " (locked)"), "abc, 1/4, 25.0% (locked)");
+        assert_eq!(a.format_with(" - ", " [L]"), a.to_string());
+    }
+
+    #[test]
+    fn rel_vec_random_pair_uniform() {
+        let rv = RelVec::from_strs(&["a", "b", "c", "d", "e"]);
+        let mut rng = StdRng::seed_from_u64(332);
+        let mut counts: HashMap<(usize, usize), u32> = HashMap::new();
+
+        for _ in 0..20000 {
+            let (a, b) = rv.random_pair_with(&mut rng).unwrap();
+
+            assert_ne!(a, b);
+            *counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+        }
+
+        // 10 unordered pairs, so about 2000 draws each
+        assert_eq!(counts.len(), 10);
+        assert!(counts.values().all(|&c| c > 1800 && c < 2200));
+    }
+
+    #[test]
+    fn rel_vec_active_len() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+        rv[1].locked = true;
+        rv[3].locked = true;
+
+        assert_eq!(rv.active_len(), 2);
+        assert_eq!(rv.locked_len(), 2);
+        assert_eq!(rv.active_len(), rv.reduced().len());
+
+        rv[3].locked = false;
+
+        assert_eq!(rv.active_len(), 3);
+        assert_eq!(rv.locked_len(), 1);
+        assert_eq!(RelVec::new().active_len(), 0);
+    }
+
+    #[test]
+    fn rel_vec_sync_names() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(2, 0).unwrap();
+
+        let summary = rv.sync_names(&[
+            "cde".to_string(),
+            "def".to_string(),
+            "abc".to_string(),
+            "def".to_string(),
+        ]);
+
+        assert_eq!(
+            summary,
+            SyncSummary {
+                added: 1,
+                removed: 1
+            }
+        );
+        assert_eq!(rv, RelVec::from_strs(&["abc", "cde", "def"]));
+        assert_eq!((rv[0].wins, rv[0].votes), (1, 2));
+        assert_eq!((rv[1].wins, rv[1].votes), (1, 1));
+        assert_eq!((rv[2].wins, rv[2].votes), (0, 0));
+    }
+
+    #[test]
+    fn rel_vec_save_toml() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(2, 0).unwrap();
+        rv[1].locked = true;
+        rv[1].weight = 2.5;
+        rv[2].tags = ["movie".to_string()].to_vec();
+
+        rv.save_toml("_rel_vec_save_toml.toml").unwrap();
+
+        let content = fs::read_to_string("_rel_vec_save_toml.toml").unwrap();
+        let b = RelVec::load_toml("_rel_vec_save_toml.toml").unwrap();
+
+        fs::remove_file("_rel_vec_save_toml.toml").unwrap();
+
+        assert!(content.starts_with("[[entries]]\n"));
+        assert!(content.contains("[entries.h]\nbcd = 1\n"));
+        assert_eq!(rv, b);
+        for (a, b) in rv.iter().zip(b.iter()) {
+            assert_eq!((a.wins, a.votes, a.locked), (b.wins, b.votes, b.locked));
+            assert_eq!(
+                (&a.beaten, a.weight, &a.tags),
+                (&b.beaten, b.weight, &b.tags)
+            );
+        }
+    }
+
+    #[test]
+    fn rel_vec_save_toml_empty() {
+        RelVec::new()
+            .save_toml("_rel_vec_save_toml_empty.toml")
+            .unwrap();
+
+        let b = RelVec::load_toml("_rel_vec_save_toml_empty.toml").unwrap();
+
+        fs::remove_file("_rel_vec_save_toml_empty.toml").unwrap();
+
+        assert_eq!(b, RelVec::new());
+    }
+
+    #[test]
+    fn rel_vec_drain_filter() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 3),
+                RelEntry::new("bcd".to_owned(), 2, 3),
+                RelEntry::new("cde".to_owned(), 0, 2),
+                RelEntry::new("def".to_owned(), 1, 2),
+                RelEntry::new("efg".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let removed = rv.drain_filter(|e| e.votes > 0 && e.wins == 0);
+
+        assert_eq!(
+            removed
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<&str>>(),
+            ["abc", "cde"].to_vec()
+        );
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["bcd", "def", "efg"].to_vec()
+        );
+        assert!(rv.drain_filter(|_| false).is_empty());
+        assert_eq!(rv.len(), 3);
+    }
+
+    #[test]
+    fn rel_vec_next_matchup() {
+        let mut a = RelVec::from_strs(&["a", "b", "c", "d", "e", "f"]);
+        a.seed(3);
+        let mut b = a.clone();
+
+        let m = a.next_matchup(&VoteStrategy::Random).unwrap();
+
+        assert_eq!(Some((m.left, m.right)), b.next_pair(&VoteStrategy::Random));
+        assert_ne!(m.left, m.right);
+        assert_eq!(Matchup::from((m.left, m.right)), m);
+        assert_eq!(m.left_entry(&a), &a[m.left]);
+        assert_eq!(m.right_entry(&a).name, a[m.right].name);
+        assert_eq!(
+            RelVec::from_strs(&["a"]).next_matchup(&VoteStrategy::Random),
+            None
+        );
+    }
+
+    #[test]
+    fn rel_vec_vote_overflow() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), u32::MAX - 1, u32::MAX - 1),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.vote(0, 1).unwrap();
+
+        assert_eq!((rv[0].wins, rv[0].votes), (u32::MAX, u32::MAX));
+        assert!(matches!(rv.vote(0, 1), Err(Error::Overflow(name)) if name == "abc"));
+        assert!(matches!(rv.vote(1, 0), Err(Error::Overflow(name)) if name == "abc"));
+        assert_eq!((rv[0].wins, rv[0].votes), (u32::MAX, u32::MAX));
+        assert_eq!((rv[1].wins, rv[1].votes), (0, 1));
+        assert_eq!(rv.comparisons_done(), 1);
+    }
+
+    #[test]
+    fn rel_vec_estimated_remaining_comparisons() {
+        assert_eq!(RelVec::new().estimated_remaining_comparisons(), 0);
+        assert_eq!(
+            RelVec::from_strs(&["a"]).estimated_remaining_comparisons(),
+            0
+        );
+        assert_eq!(
+            RelVec::from_strs(&["a", "b"]).estimated_remaining_comparisons(),
+            2
+        );
+        assert_eq!(
+            RelVec::from_strs(&["a", "b", "c"]).estimated_remaining_comparisons(),
+            5
+        );
+
+        let mut rv = RelVec::from_strs(&["a", "b", "c", "d", "e"]);
+        assert_eq!(rv.estimated_remaining_comparisons(), 12);
+
+        rv.vote(0, 1).unwrap();
+        rv.vote(2, 3).unwrap();
+        assert_eq!(rv.estimated_remaining_comparisons(), 10);
+
+        rv[4].locked = true;
+        assert_eq!(rv.estimated_remaining_comparisons(), 6);
+
+        for _ in 0..10 {
+            rv.vote(0, 1).unwrap();
+        }
+        assert_eq!(rv.estimated_remaining_comparisons(), 0);
+
+        let mut rv = RelVec::from_strs(&["a", "b", "c"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(0, 1).unwrap();
+        rv.disable("a").unwrap();
+        assert_eq!(rv.estimated_remaining_comparisons(), 1);
+    }
+
+    #[test]
+    fn rel_vec_sort_percentage_locked_first() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 3, 4),
+                RelEntry {
+                    name: "bcd".to_string(),
+                    wins: 1,
+                    votes: 4,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("cde".to_owned(), 4, 4),
+                RelEntry {
+                    name: "def".to_string(),
+                    wins: 2,
+                    votes: 4,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("efg".to_owned(), 0, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.sort_percentage_locked_first();
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["def", "bcd", "cde", "abc", "efg"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_save_jsonl() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv[2].locked = true;
+
+        rv.save_jsonl("_rel_vec_save_jsonl.txt").unwrap();
+
+        let content = fs::read_to_string("_rel_vec_save_jsonl.txt").unwrap();
+        let b = RelVec::load_jsonl("_rel_vec_save_jsonl.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save_jsonl.txt").unwrap();
+
+        assert_eq!(content.lines().count(), 3);
+        for (line, e) in content.lines().zip(rv.iter()) {
+            let parsed: RelEntry = serde_json::from_str(line).unwrap();
+            assert_eq!(&parsed, e);
+        }
+        assert_eq!(rv, b);
+        for (a, b) in rv.iter().zip(b.iter()) {
+            assert_eq!((a.wins, a.votes, a.locked), (b.wins, b.votes, b.locked));
+            assert_eq!(a.beaten, b.beaten);
+        }
+    }
+
+    #[test]
+    fn rel_vec_rename() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(1, 0).unwrap();
+
+        rv.rename("abc", "xyz".to_string()).unwrap();
+
+        assert_eq!(rv[0].name, "xyz");
+        assert_eq!((rv[0].wins, rv[0].votes), (1, 2));
+        assert_eq!(rv[0].wins_against("bcd"), 1);
+        assert_eq!(rv[1].wins_against("xyz"), 1);
+        assert_eq!(rv[1].wins_against("abc"), 0);
+        assert_eq!(rv.head_to_head(0, 1), (1, 1));
+        rv.rename("xyz", "xyz".to_string()).unwrap();
+    }
+
+    #[test]
+    fn rel_vec_rename_not_found() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+
+        assert!(matches!(
+            rv.rename("cde", "xyz".to_string()),
+            Err(Error::NotFound(name)) if name == "cde"
+        ));
+        assert_eq!(rv, RelVec::from_strs(&["abc", "bcd"]));
+    }
+
+    #[test]
+    fn rel_vec_rename_collision() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+
+        assert!(matches!(
+            rv.rename("abc", "bcd".to_string()),
+            Err(Error::DuplicateName(name)) if name == "bcd"
+        ));
+        assert_eq!(rv, RelVec::from_strs(&["abc", "bcd"]));
+    }
+
+    #[test]
+    fn rel_vec_informative_pair() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 10, 20),
+                RelEntry::new("bcd".to_owned(), 11, 20),
+                RelEntry::new("cde".to_owned(), 1, 2),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 1,
+                    votes: 2,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("def".to_owned(), 1, 2),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        for _ in 0..10 {
+            let (a, b) = rv.informative_pair().unwrap();
+            assert_eq!((a.min(b), a.max(b)), (2, 4));
+        }
+
+        rv.add("efg".to_string());
+        rv.add("fgh".to_string());
+
+        let (a, b) = rv.informative_pair().unwrap();
+        assert_eq!((a.min(b), a.max(b)), (5, 6));
+
+        assert_eq!(RelVec::from_strs(&["abc"]).informative_pair(), None);
+    }
+
+    #[test]
+    fn rel_vec_vote_weighted() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+
+        rv.vote_weighted(0, 1, 1).unwrap();
+
+        assert_eq!((rv[0].wins, rv[0].votes), (1, 1));
+        assert_eq!((rv[1].wins, rv[1].votes), (0, 1));
+
+        rv.vote_weighted(1, 0, 3).unwrap();
+
+        assert_eq!((rv[0].wins, rv[0].votes), (1, 4));
+        assert_eq!((rv[1].wins, rv[1].votes), (3, 4));
+        assert_eq!(rv.head_to_head(0, 1), (1, 3));
+        assert_eq!(rv[1].percentage(), 75.0);
+        assert_eq!(rv.comparisons_done(), 2);
+        assert!(matches!(
+            rv.vote_weighted(1, 0, u32::MAX),
+            Err(Error::Overflow(name)) if name == "bcd"
+        ));
+        assert_eq!((rv[1].wins, rv[1].votes), (3, 4));
+
+        assert!(matches!(
+            rv.vote_weighted(1, 1, 1),
+            Err(Error::SameEntry(name)) if name == "bcd"
+        ));
+        assert!(matches!(rv.vote_weighted(1, 0, 0), Err(Error::ZeroMargin)));
+        assert_eq!((rv[1].wins, rv[1].votes), (3, 4));
+        assert_eq!(rv.comparisons_done(), 2);
+    }
+
+    #[test]
+    fn rel_vec_tiers() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("bcd".to_owned(), 4, 4),
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 0, 0),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 4,
+                    votes: 4,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("efg".to_owned(), 2, 4),
+                RelEntry::new("fgh".to_owned(), 9, 10),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(
+            rv.tiers(&[90.0, 70.0, 50.0]),
+            [
+                [1, 6].to_vec(),
+                [2].to_vec(),
+                [5].to_vec(),
+                [0].to_vec(),
+                [3].to_vec()
+            ]
+            .to_vec()
+        );
+        assert_eq!(rv.tiers(&[50.0, 90.0, 70.0]), rv.tiers(&[90.0, 70.0, 50.0]));
+        assert_eq!(
+            rv.tiers(&[]),
+            [[1, 6, 2, 5, 0].to_vec(), [3].to_vec()].to_vec()
+        );
+
+        let mut rv = rv;
+        rv.disable("cde").unwrap();
+        rv.disable("def").unwrap();
+        rv[0].weight = 0.0;
+
+        assert_eq!(
+            rv.tiers(&[90.0, 70.0, 50.0]),
+            [
+                [1, 6].to_vec(),
+                [2].to_vec(),
+                [5].to_vec(),
+                [0].to_vec(),
+                [3].to_vec()
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_diff() {
+        let a = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 2),
+                RelEntry::new("bcd".to_owned(), 1, 2),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 2, 2),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        let mut b = a.clone();
+        b.remove(|e| e.name == "bcd");
+        b.vote(0, 1).unwrap();
+        b[2].locked = true;
+        b.add("efg".to_string());
+
+        let d = diff(&a, &b);
+
+        assert_eq!(
+            d,
+            RelDiff {
+                added: ["efg".to_string()].to_vec(),
+                removed: ["bcd".to_string()].to_vec(),
+                changed: [
+                    EntryChange {
+                        name: "abc".to_string(),
+                        before: EntryStats {
+                            wins: 1,
+                            votes: 2,
+                            locked: false
+                        },
+                        after: EntryStats {
+                            wins: 2,
+                            votes: 3,
+                            locked: false
+                        },
+                    },
+                    EntryChange {
+                        name: "cde".to_string(),
+                        before: EntryStats {
+                            wins: 0,
+                            votes: 0,
+                            locked: false
+                        },
+                        after: EntryStats {
+                            wins: 0,
+                            votes: 1,
+                            locked: false
+                        },
+                    },
+                    EntryChange {
+                        name: "def".to_string(),
+                        before: EntryStats {
+                            wins: 2,
+                            votes: 2,
+                            locked: false
+                        },
+                        after: EntryStats {
+                            wins: 2,
+                            votes: 2,
+                            locked: true
+                        },
+                    },
+                ]
+                .to_vec(),
+            }
+        );
+        assert_eq!(diff(&a, &a), RelDiff::default());
+
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(serde_json::from_str::<RelDiff>(&json).unwrap(), d);
+    }
+
+    #[test]
+    fn rel_vec_build_index() {
+        let names: Vec<String> = (0..20_000).map(|i| format!("entry {}", i)).collect();
+        let mut rv = RelVec::create(names.clone());
+        rv.build_index();
+
+        for (i, name) in names.iter().enumerate() {
+            assert_eq!(rv.position(name), Some(i));
+        }
+        assert!(!rv.contains("entry 20000"));
+
+        rv.remove(|e| e.name == "entry 0");
+        rv.add("new".to_string());
+        rv.rename("entry 1", "first".to_string()).unwrap();
+
+        assert_eq!(rv.position("entry 2"), Some(1));
+        assert_eq!(rv.position("new"), Some(19_999));
+        assert_eq!(rv.position("first"), Some(0));
+        assert!(!rv.contains("entry 0"));
+        assert!(!rv.contains("entry 1"));
+
+        rv.sort_name();
+
+        for (i, e) in rv.iter().enumerate() {
+            assert_eq!(rv.position(&e.name), Some(i));
+        }
+
+        rv.drop_index();
+
+        assert_eq!(rv.position("new"), rv.iter().position(|e| e.name == "new"));
+    }
+
+    #[test]
+    fn rel_vec_random_group() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def", "efg"]);
+        rv[1].locked = true;
+
+        for _ in 0..20 {
+            let mut group = rv.random_group(4).unwrap();
+            group.sort_unstable();
+
+            assert_eq!(group, [0, 2, 3, 4].to_vec());
+        }
+
+        let group = rv.random_group(3).unwrap();
+        assert_eq!(group.len(), 3);
+        assert!(group.iter().all(|&i| i != 1));
+        assert_eq!(rv.random_group(5), None);
+    }
+
+    #[test]
+    fn rel_vec_vote_group() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+
+        rv.vote_group(2, &[0, 2, 3]).unwrap();
+
+        assert_eq!((rv[2].wins, rv[2].votes), (2, 2));
+        assert_eq!((rv[0].wins, rv[0].votes), (0, 1));
+        assert_eq!((rv[1].wins, rv[1].votes), (0, 0));
+        assert_eq!((rv[3].wins, rv[3].votes), (0, 1));
+        assert_eq!(rv.head_to_head(2, 0), (1, 0));
+        assert_eq!(rv.head_to_head(2, 3), (1, 0));
+        assert_eq!(rv.comparisons_done(), 2);
+
+        rv[3].votes = u32::MAX;
+
+        assert!(matches!(
+            rv.vote_group(2, &[0, 1, 3]),
+            Err(Error::Overflow(name)) if name == "def"
+        ));
+        assert_eq!((rv[2].wins, rv[2].votes), (2, 2));
+        assert_eq!((rv[0].wins, rv[0].votes), (0, 1));
+        assert_eq!((rv[1].wins, rv[1].votes), (0, 0));
+        assert_eq!(rv.comparisons_done(), 2);
+    }
+
+    #[test]
+    fn rel_vec_save_checked() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+
+        rv.save_checked("_rel_vec_save_checked.txt").unwrap();
+
+        let b = RelVec::load_checked("_rel_vec_save_checked.txt").unwrap();
+
+        let mut content = fs::read("_rel_vec_save_checked.txt").unwrap();
+        content[8] ^= 1;
+        fs::write("_rel_vec_save_checked.txt", &content).unwrap();
+        let corrupted = RelVec::load_checked("_rel_vec_save_checked.txt");
+
+        rv.save("_rel_vec_save_checked.txt").unwrap();
+        let unchecked = RelVec::load_checked("_rel_vec_save_checked.txt");
+
+        fs::remove_file("_rel_vec_save_checked.txt").unwrap();
+
+        assert_eq!(rv, b);
+        assert_eq!((b[0].wins, b[0].votes), (1, 1));
+        assert!(matches!(corrupted, Err(Error::ChecksumMismatch)));
+        assert!(matches!(unchecked, Err(Error::ChecksumMismatch)));
+    }
+
+    #[test]
+    fn rel_vec_equal_pair_fraction() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 3, 4),
+                RelEntry::new("bcd".to_owned(), 1, 3),
+                RelEntry::new("cde".to_owned(), 3_000_000_000, 4_000_000_000),
+                RelEntry::new("def".to_owned(), 0, 0),
+                RelEntry::new("efg".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        for _ in 0..10 {
+            let (a, b) = rv.equal_pair().unwrap();
+            assert_eq!((a.min(b), a.max(b)), (0, 2));
+        }
+
+        rv[2].locked = true;
+
+        assert_eq!(rv.equal_pair(), None);
+    }
+
+    #[test]
+    fn rel_vec_equal_pair_tol() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 3),
+                RelEntry::new("bcd".to_owned(), 33, 100),
+                RelEntry::new("cde".to_owned(), 1, 2),
+                RelEntry::new("def".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.equal_pair(), None);
+        assert_eq!(rv.equal_pair_tol(0.1), None);
+
+        let (a, b) = rv.equal_pair_tol(0.5).unwrap();
+        assert_eq!((a.min(b), a.max(b)), (0, 1));
+        assert!(rv.equal_pair_tol(20.0).is_some());
+        assert_eq!(rv.equal_pair_tol(0.0), None);
+    }
+
+    #[test]
+    fn rel_vec_predicted_winner() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 3, 4),
+                RelEntry::new("bcd".to_owned(), 1, 4),
+                RelEntry::new("cde".to_owned(), 6, 8),
+                RelEntry::new("def".to_owned(), 0, 0),
+                RelEntry::new("efg".to_owned(), 0, 3),
+                RelEntry::new("fgh".to_owned(), 0, 5),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.predicted_winner(0, 1), (0.75, 0.25));
+        assert_eq!(rv.predicted_winner(1, 0), (0.25, 0.75));
+        assert_eq!(rv.predicted_winner(0, 2), (0.5, 0.5));
+        assert_eq!(rv.predicted_winner(0, 3), (0.5, 0.5));
+        assert_eq!(rv.predicted_winner(3, 3), (0.5, 0.5));
+        assert_eq!(rv.predicted_winner(0, 4), (1.0, 0.0));
+        assert_eq!(rv.predicted_winner(4, 5), (0.5, 0.5));
+    }
+
+    #[test]
+    fn rel_vec_lock_above() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 9, 10),
+                RelEntry::new("bcd".to_owned(), 2, 2),
+                RelEntry::new("cde".to_owned(), 8, 10),
+                RelEntry::new("def".to_owned(), 5, 10),
+                RelEntry::new("efg".to_owned(), 0, 0),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 10,
+                    votes: 10,
+                    locked: true,
+                    ..Default::default()
+                },
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.lock_above(80.0, 5), 2);
+        assert_eq!(
+            rv.iter().map(|e| e.locked).collect::<Vec<bool>>(),
+            [true, false, true, false, false, true].to_vec()
+        );
+        assert_eq!(rv.lock_above(80.0, 5), 0);
+        assert_eq!(rv.lock_above(0.0, 0), 2);
+        assert!(!rv[4].locked);
+    }
+
+    #[test]
+    fn rel_vec_unlock_below() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 9, 10),
+                RelEntry::new("bcd".to_owned(), 2, 10),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 5, 10),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        rv.iter_mut().for_each(|e| e.locked = true);
+
+        assert_eq!(rv.unlock_below(60.0), 2);
+        assert_eq!(
+            rv.iter().map(|e| e.locked).collect::<Vec<bool>>(),
+            [true, false, true, false].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_sample() {
+        let names: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let mut rv = RelVec::create(names);
+        rv.seed(11);
+        let mut b = rv.clone();
+
+        let sample = rv.sample(10);
+        let mut distinct = sample.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+
+        assert_eq!(sample.len(), 10);
+        assert_eq!(distinct.len(), 10);
+        assert!(sample.iter().all(|&i| i < 100));
+        assert_eq!(b.sample(10), sample);
+        assert_ne!(rv.sample(10), sample);
+        assert_eq!(rv.sample(100), (0..100).collect::<Vec<usize>>());
+        assert_eq!(rv.sample(200).len(), 100);
+        assert!(RelVec::new().sample(3).is_empty());
+    }
+
+    #[test]
+    fn rel_vec_to_svg() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 4),
+                RelEntry::new("<b & c>".to_owned(), 4, 4),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 2, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        let svg = rv.to_svg(200, 90);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect ").count(), 3);
+        assert_eq!(svg.matches("<text ").count(), 3);
+        assert_eq!(svg.matches("</text>").count(), 3);
+        assert!(svg.contains(
+            "<rect x=\"0\" y=\"0.00\" width=\"200.00\" height=\"27.00\" fill=\"steelblue\"/>"
+        ));
+        assert!(svg.contains(">&lt;b &amp; c&gt;</text>"));
+        assert!(!svg.contains("cde"));
+
+        let names: Vec<&str> = svg
+            .lines()
+            .filter_map(|l| l.strip_suffix("</text>"))
+            .map(|l| &l[l.rfind('>').unwrap() + 1..])
+            .collect();
+        assert_eq!(names, ["&lt;b &amp; c&gt;", "def", "abc"].to_vec());
+    }
+
+    #[test]
+    fn rel_vec_min_pair_locked() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 3, 5),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 1,
+                    votes: 1,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("cde".to_owned(), 2, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        rv.seed(5);
+
+        for _ in 0..50 {
+            let (a, b) = rv.min_pair().unwrap();
+
+            assert_eq!(a, 0);
+            assert!(b == 1 || b == 3);
+        }
+    }
+
+    #[test]
+    fn rel_vec_min_pair_first() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 3, 5),
+                RelEntry {
+                    name: "locked".to_string(),
+                    wins: 0,
+                    votes: 0,
+                    locked: true,
+                    ..Default::default()
+                },
+                RelEntry::new("bcd".to_owned(), 1, 2),
+                RelEntry::new("cde".to_owned(), 2, 4),
+                RelEntry::new("def".to_owned(), 0, 2),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.min_pair_first(), Some((2, 4)));
+
+        rv[4].votes = 3;
+
+        assert_eq!(rv.min_pair_first(), Some((2, 4)));
+
+        rv[4].votes = 5;
+
+        assert_eq!(rv.min_pair_first(), Some((2, 3)));
+        assert_eq!(RelVec::from_strs(&["abc"]).min_pair_first(), None);
+    }
+
+    #[test]
+    fn rel_vec_skip() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        let before = rv.clone();
+
+        rv.skip(1, 2);
+
+        assert_eq!(rv.skips(), 1);
+        assert_eq!(rv.comparisons_done(), 1);
+        for (a, b) in rv.iter().zip(before.iter()) {
+            assert_eq!((a.wins, a.votes, &a.beaten), (b.wins, b.votes, &b.beaten));
+        }
+        assert!(rv.recently_skipped(2, 1));
+        assert!(!rv.recently_skipped(0, 1));
+
+        let mut calls = 0;
+        let pair = rv.choose_pair(|_| {
+            calls += 1;
+            if calls == 1 {
+                Some((2, 1))
+            } else {
+                Some((0, 2))
+            }
+        });
+
+        assert_eq!(pair, Some((0, 2)));
+
+        rv.rename("cde", "aaa".to_string()).unwrap();
+
+        assert!(rv.recently_skipped(1, 2));
+        assert!(rv.recently_skipped(2, 1));
+        assert_eq!(rv.choose_pair(|_| Some((1, 2))), Some((1, 2)));
+
+        for _ in 0..9 {
+            rv.vote(0, 2).unwrap();
+        }
+
+        assert!(!rv.recently_skipped(1, 2));
+    }
+
+    #[test]
+    fn rel_vec_transitivity_score() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+
+        assert_eq!(rv.transitivity_score(), 1.0);
+
+        for a in 0..4 {
+            for b in a + 1..4 {
+                rv.vote(a, b).unwrap();
+            }
+        }
+
+        assert_eq!(rv.transitivity_score(), 1.0);
+
+        rv.vote(3, 0).unwrap();
+
+        assert_eq!(rv.transitivity_score(), 1.0);
+
+        rv.vote(3, 0).unwrap();
+
+        assert_eq!(rv.transitivity_score(), 0.5);
+    }
+
+    #[test]
+    fn rel_vec_transitivity_score_cyclic() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(1, 2).unwrap();
+        rv.vote(2, 0).unwrap();
+
+        assert_eq!(rv.transitivity_score(), 0.0);
+    }
+
+    #[test]
+    fn rel_entry_meta() {
+        let mut e = RelEntry::new("abc".to_owned(), 1, 2);
+
+        assert_eq!(e.get_meta("url"), None);
+        assert_eq!(e.set_meta("url".to_string(), "a".to_string()), None);
+        assert_eq!(
+            e.set_meta("url".to_string(), "https://example.com".to_string()),
+            Some("a".to_string())
+        );
+        e.set_meta("note".to_string(), "\"quoted\"".to_string());
+
+        let json = serde_json::to_string(&e).unwrap();
+        let b: RelEntry = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            json,
+            "{\"n\":\"abc\",\"w\":1,\"v\":2,\"l\":false,\"m\":{\"note\":\"\\\"quoted\\\"\",\"url\":\"https://example.com\"}}"
+        );
+        assert_eq!(b.meta, e.meta);
+        assert_eq!(b.get_meta("url"), Some("https://example.com"));
+
+        let old: RelEntry =
+            serde_json::from_str("{\"n\":\"abc\",\"w\":1,\"v\":2,\"l\":false}").unwrap();
+        assert!(old.meta.is_empty());
+    }
+
+    #[test]
+    fn rel_vec_normalize_votes() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 3, 4),
+                RelEntry::new("bcd".to_owned(), 1, 3),
+                RelEntry::new("cde".to_owned(), 50, 100),
+                RelEntry::new("def".to_owned(), 0, 0),
+                RelEntry::new("efg".to_owned(), 7, 7),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        let before: Vec<f64> = rv.iter().map(|e| e.percentage()).collect();
+
+        rv.normalize_votes(20);
+
+        assert_eq!(
+            rv.iter()
+                .map(|e| (e.wins, e.votes))
+                .collect::<Vec<(u32, u32)>>(),
+            [(15, 20), (7, 20), (10, 20), (0, 0), (20, 20)].to_vec()
+        );
+        for (e, p) in rv.iter().zip(before).filter(|(e, _)| e.votes > 0) {
+            assert!((e.percentage() - p).abs() <= 2.5);
+        }
+        assert!(rv.validate().is_ok());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn rel_vec_save_async() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv.seed(3);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let b = runtime
+            .block_on(async {
+                rv.save_async("_rel_vec_save_async.txt").await?;
+                RelVec::load_async("_rel_vec_save_async.txt").await
+            })
+            .unwrap();
+        let sync = RelVec::load("_rel_vec_save_async.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save_async.txt").unwrap();
+
+        assert_eq!(rv, b);
+        assert_eq!((b[0].wins, b[0].votes), (1, 1));
+        assert_eq!(b.head_to_head(0, 1), (1, 0));
+        assert_eq!(rv.clone().random_pair(), b.clone().random_pair());
+        assert_eq!(sync, b);
     }
 
     #[test]
-    fn rel_vec_sort_percentage() {
+    fn rel_entry_deep_eq() {
+        let a = RelEntry::new("abc".to_owned(), 1, 2);
+        let mut b = a.clone();
+
+        assert!(a.deep_eq(&b));
+
+        b.wins = 2;
+        assert_eq!(a, b);
+        assert!(!a.deep_eq(&b));
+
+        let mut c = a.clone();
+        c.locked = true;
+        assert_eq!(a, c);
+        assert!(!a.deep_eq(&c));
+        assert!(!a.deep_eq(&RelEntry::new("bcd".to_owned(), 1, 2)));
+    }
+
+    #[test]
+    fn rel_vec_deep_eq() {
+        let a = RelVec::from_strs(&["abc", "bcd"]);
+        let mut b = a.clone();
+
+        assert!(a.deep_eq(&b));
+
+        b.vote(0, 1).unwrap();
+
+        assert_eq!(a, b);
+        assert!(!a.deep_eq(&b));
+        assert!(!a.deep_eq(&RelVec::from_strs(&["abc"])));
+    }
+
+    #[test]
+    fn rel_vec_reset_with() {
         let mut rv = RelVec {
             inner: [
-                RelEntry::new("bec".to_owned(), 1, 1),
-                RelEntry::new("ads".to_owned(), 1, 2),
-                RelEntry::new("foo".to_owned(), 3, 4),
-                RelEntry::new("bar".to_owned(), 1, 4),
-                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("Episode 1".to_owned(), 1, 2),
+                RelEntry::new("Movie".to_owned(), 2, 3),
+                RelEntry {
+                    name: "Episode 2".to_string(),
+                    wins: 1,
+                    votes: 1,
+                    locked: true,
+                    ..Default::default()
+                },
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
+        let mut unlocked = rv.clone();
+        let mut all = rv.clone();
 
-        rv.sort_percentage();
+        rv.reset_with(|e| e.name.starts_with("Episode"));
+        unlocked.reset_unlocked();
+        all.reset_all();
 
         assert_eq!(
-            rv,
-            RelVec {
-                inner: [
-                    RelEntry::new("bec".to_owned(), 1, 1),
-                    RelEntry::new("foo".to_owned(), 3, 4),
-                    RelEntry::new("ads".to_owned(), 1, 2),
-                    RelEntry::new("bar".to_owned(), 1, 4),
-                    RelEntry::new("abc".to_owned(), 0, 0),
-                ]
-                .to_vec(),
-                rng: rand::thread_rng(),
+            rv.iter()
+                .map(|e| (e.wins, e.votes, e.locked))
+                .collect::<Vec<_>>(),
+            [(0, 0, false), (2, 3, false), (0, 0, false)].to_vec()
+        );
+        assert_eq!(
+            unlocked
+                .iter()
+                .map(|e| (e.wins, e.votes, e.locked))
+                .collect::<Vec<_>>(),
+            [(0, 0, false), (0, 0, false), (1, 1, true)].to_vec()
+        );
+        assert!(all.iter().all(|e| e.votes == 0 && !e.locked));
+
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(0, 2).unwrap();
+        rv.vote(1, 2).unwrap();
+
+        rv.reset_with(|e| e.name == "cde");
+
+        assert_eq!(rv.head_to_head(0, 1), (1, 0));
+        assert_eq!(rv[0].wins_against("cde"), 0);
+        assert_eq!(rv[1].wins_against("cde"), 0);
+        assert!(rv[2].beaten.is_empty());
+    }
+
+    #[test]
+    fn rel_vec_inconsistent_entries() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+        for _ in 0..3 {
+            rv.vote(0, 2).unwrap();
+            rv.vote(0, 3).unwrap();
+            rv.vote(1, 2).unwrap();
+        }
+        rv.vote(1, 0).unwrap();
+        rv.vote(1, 0).unwrap();
+        rv.vote(3, 1).unwrap();
+        rv.vote(3, 1).unwrap();
+
+        assert!(rv[1].percentage() > rv[3].percentage());
+        assert_eq!(rv.inconsistent_entries(0.3), [1].to_vec());
+        assert_eq!(rv.inconsistent_entries(0.5), Vec::<usize>::new());
+        assert_eq!(rv.inconsistent_entries(0.0), [0, 1].to_vec());
+        assert!(RelVec::from_strs(&["abc"])
+            .inconsistent_entries(0.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn rel_vec_from_dir() {
+        fs::create_dir("_rel_vec_from_dir").unwrap();
+        fs::write("_rel_vec_from_dir/series.txt", "abc\nbcd\n").unwrap();
+        fs::write("_rel_vec_from_dir/movies.txt", "cde\n").unwrap();
+        fs::write("_rel_vec_from_dir/notes.md", "def\n").unwrap();
+        fs::create_dir("_rel_vec_from_dir/more.txt").unwrap();
+
+        let rv = RelVec::from_dir("_rel_vec_from_dir").unwrap();
+
+        fs::remove_dir_all("_rel_vec_from_dir").unwrap();
+
+        assert_eq!(rv, RelVec::from_strs(&["cde", "abc", "bcd"]));
+        assert_eq!(
+            rv.iter()
+                .map(|e| e.tags.clone())
+                .collect::<Vec<Vec<String>>>(),
+            [
+                ["movies".to_string()].to_vec(),
+                ["series".to_string()].to_vec(),
+                ["series".to_string()].to_vec()
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_entry_smoothed_percentage() {
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 0, 0).smoothed_percentage(1.0),
+            50.0
+        );
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 1, 1).smoothed_percentage(1.0),
+            200.0 / 3.0
+        );
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 3, 4).smoothed_percentage(0.0),
+            75.0
+        );
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 0, 2).smoothed_percentage(2.0),
+            100.0 / 3.0
+        );
+    }
+
+    #[test]
+    fn rel_vec_sort_smoothed() {
+        let mut rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 1),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry::new("cde".to_owned(), 50, 60),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.sort_smoothed(1.0);
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["cde", "abc", "bcd"].to_vec()
+        );
+
+        rv.sort_percentage();
+
+        assert_eq!(rv[0].name, "abc");
+    }
+
+    #[test]
+    fn rel_vec_from_unique() {
+        fs::write("_rel_vec_from_unique.txt", "abc\nbcd\nabc\ncde\nbcd\nabc\n").unwrap();
+
+        let (rv, skipped) = RelVec::from_unique("_rel_vec_from_unique.txt").unwrap();
+
+        fs::remove_file("_rel_vec_from_unique.txt").unwrap();
+
+        assert_eq!(rv, RelVec::from_strs(&["abc", "bcd", "cde"]));
+        assert_eq!(skipped, 3);
+    }
+
+    #[test]
+    fn rel_vec_borda_scores() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+        for a in 0..4 {
+            for b in a + 1..4 {
+                rv.vote(b, a).unwrap();
             }
+        }
+        rv.vote(0, 3).unwrap();
+
+        assert_eq!(rv.borda_scores(), [1, 1, 2, 3].to_vec());
+
+        rv.sort_borda();
+
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["def", "cde", "abc", "bcd"].to_vec()
         );
+        assert_eq!(rv.borda_scores(), [3, 2, 1, 1].to_vec());
+
+        rv.remove(|e| e.name == "abc");
+
+        assert_eq!(rv.borda_scores(), [2, 1, 0].to_vec());
     }
 
     #[test]
-    fn rel_vec_min_votes() {
+    fn rel_vec_save_v2() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+        rv.vote(0, 1).unwrap();
+        rv[1].locked = true;
+        rv[1].tags = ["movie".to_string()].to_vec();
+
+        rv.save_v2("_rel_vec_save_v2.txt").unwrap();
+
+        let content = fs::read_to_string("_rel_vec_save_v2.txt").unwrap();
+        let b = RelVec::load("_rel_vec_save_v2.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save_v2.txt").unwrap();
+
+        assert!(content.starts_with("{\n  \"version\": 2,\n  \"entries\": [\n    {\n      \"name\": \"abc\",\n      \"wins\": 1,\n      \"votes\": 1,\n      \"locked\": false,\n      \"beaten\": {\n        \"bcd\": 1\n"));
+        assert!(rv.deep_eq(&b));
+        assert_eq!(b[0].beaten, rv[0].beaten);
+        assert_eq!(b[1].tags, rv[1].tags);
+    }
+
+    #[test]
+    fn rel_vec_load_versions() {
+        fs::write(
+            "_rel_vec_load_versions.txt",
+            "[{\"n\":\"abc\",\"w\":1,\"v\":2,\"l\":true}]",
+        )
+        .unwrap();
+        let v1 = RelVec::load("_rel_vec_load_versions.txt").unwrap();
+
+        fs::write(
+            "_rel_vec_load_versions.txt",
+            "{\"version\":2,\"entries\":[{\"name\":\"abc\",\"wins\":1,\"votes\":2,\"locked\":true}]}",
+        )
+        .unwrap();
+        let v2 = RelVec::load("_rel_vec_load_versions.txt").unwrap();
+
+        fs::write(
+            "_rel_vec_load_versions.txt",
+            "{\"version\":3,\"entries\":[]}",
+        )
+        .unwrap();
+        let v3 = RelVec::load("_rel_vec_load_versions.txt");
+
+        fs::remove_file("_rel_vec_load_versions.txt").unwrap();
+
+        assert!(v1.deep_eq(&v2));
+        assert_eq!((v2[0].wins, v2[0].votes, v2[0].locked), (1, 2, true));
+        assert!(matches!(v3, Err(Error::UnsupportedVersion(3))));
+    }
+
+    #[test]
+    fn rel_vec_load_invalid_entry() {
+        fs::write(
+            "_rel_vec_load_invalid_entry.txt",
+            "[{\"n\":\"abc\",\"w\":\"x\"}]",
+        )
+        .unwrap();
+        let array = RelVec::load("_rel_vec_load_invalid_entry.txt");
+        fs::write(
+            "_rel_vec_load_invalid_entry.txt",
+            "{\"version\":2,\"entries\":[{\"name\":\"abc\",\"wins\":-1}]}",
+        )
+        .unwrap();
+        let v2 = RelVec::load("_rel_vec_load_invalid_entry.txt");
+
+        fs::remove_file("_rel_vec_load_invalid_entry.txt").unwrap();
+
+        for err in [array.unwrap_err(), v2.unwrap_err()] {
+            assert!(err.to_string().contains("invalid"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn rel_vec_leader() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("cde".to_owned(), 1, 2),
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 2, 4),
+                RelEntry::new("def".to_owned(), 2, 4),
+                RelEntry::new("efg".to_owned(), 0, 3),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.leader().map(|e| e.name.as_str()), Some("bcd"));
+
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 1, 2),
+                RelEntry::new("bcd".to_owned(), 3, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.leader().map(|e| e.name.as_str()), Some("bcd"));
+    }
+
+    #[test]
+    fn rel_vec_leader_unvoted() {
+        assert!(RelVec::new().leader().is_none());
+        assert!(RelVec::from_strs(&["abc", "bcd"]).leader().is_none());
+    }
+
+    #[test]
+    fn rel_vec_vote_log() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 1).unwrap();
+
+        assert!(rv.vote_log().is_empty());
+
+        rv.enable_vote_log();
+        rv.vote(1, 2).unwrap();
+
+        assert_eq!(rv.vote_log().len(), 1);
+
+        rv.vote(2, 0).unwrap();
+        rv.vote_group(0, &[1, 2]).unwrap();
+
+        assert_eq!(
+            rv.vote_log()
+                .iter()
+                .map(|v| (v.winner.as_str(), v.loser.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            [
+                ("bcd", "cde"),
+                ("cde", "abc"),
+                ("abc", "bcd"),
+                ("abc", "cde")
+            ]
+            .to_vec()
+        );
+        assert!(rv.vote_log().iter().all(|v| v.timestamp > 0));
+
+        rv.save_log("_rel_vec_vote_log.txt").unwrap();
+
+        let mut b = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        b.load_log("_rel_vec_vote_log.txt").unwrap();
+
+        fs::remove_file("_rel_vec_vote_log.txt").unwrap();
+
+        assert_eq!(b.vote_log(), rv.vote_log());
+
+        b.vote(0, 1).unwrap();
+
+        assert_eq!(b.vote_log().len(), 5);
+
+        rv.disable_vote_log();
+        rv.vote(0, 1).unwrap();
+
+        assert!(rv.vote_log().is_empty());
+    }
+
+    #[test]
+    fn rel_vec_vote_by_name() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+
+        rv.vote_by_name("cde", "abc").unwrap();
+
+        assert_eq!((rv[2].wins, rv[2].votes), (1, 1));
+        assert_eq!((rv[0].wins, rv[0].votes), (0, 1));
+        assert_eq!(rv.head_to_head(2, 0), (1, 0));
+
+        assert!(matches!(
+            rv.vote_by_name("xyz", "abc"),
+            Err(Error::NotFound(name)) if name == "xyz"
+        ));
+        assert!(matches!(
+            rv.vote_by_name("abc", "xyz"),
+            Err(Error::NotFound(name)) if name == "xyz"
+        ));
+        assert!(matches!(
+            rv.vote_by_name("bcd", "bcd"),
+            Err(Error::SameEntry(name)) if name == "bcd"
+        ));
+        assert_eq!(rv.comparisons_done(), 1);
+    }
+
+    #[test]
+    fn rel_vec_soft_nearest_pair() {
         let mut rv = RelVec {
             inner: [
-                RelEntry {
-                    name: "abc".to_string(),
-                    wins: 12,
-                    votes: 123,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "bcd".to_string(),
-                    wins: 125,
-                    votes: 123,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "locked".to_string(),
-                    wins: 0,
-                    votes: 0,
-                    locked: true,
-                },
-                RelEntry {
-                    name: "cde".to_string(),
-                    wins: 12,
-                    votes: 12632,
-                    locked: false,
-                },
+                RelEntry::new("abc".to_owned(), 1, 10),
+                RelEntry::new("bcd".to_owned(), 5, 10),
+                RelEntry::new("cde".to_owned(), 6, 10),
+                RelEntry::new("def".to_owned(), 9, 10),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        rv.seed(42);
+
+        for _ in 0..50 {
+            assert_eq!(rv.soft_nearest_pair(1e-9), Some((1, 2)));
+            assert_eq!(rv.soft_nearest_pair(0.0), Some((1, 2)));
+        }
+
+        let mut seen = HashMap::new();
+        for _ in 0..600 {
+            *seen.entry(rv.soft_nearest_pair(1e9).unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(seen.len(), 6);
+        assert!(seen.values().all(|&c| c > 50));
+
+        rv[0].locked = true;
+        rv[1].locked = true;
+        rv[2].locked = true;
+
+        assert_eq!(rv.soft_nearest_pair(1.0), None);
+    }
+
+    #[test]
+    fn rel_vec_load_then_sort_percentage() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 4),
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 2, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.save("_rel_vec_load_then_sort_percentage.txt").unwrap();
+
+        let b =
+            RelVec::load_then_sort_percentage("_rel_vec_load_then_sort_percentage.txt").unwrap();
+
+        fs::remove_file("_rel_vec_load_then_sort_percentage.txt").unwrap();
+
+        assert_eq!(
+            b.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["cde", "def", "bcd", "abc"].to_vec()
+        );
+    }
+
+    #[test]
+    fn rel_vec_vote_strength() {
+        for strength in 1..=3 {
+            let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+
+            rv.vote_strength(1, 0, strength).unwrap();
+
+            let s = u32::from(strength);
+            assert_eq!((rv[1].wins, rv[1].votes), (s, s));
+            assert_eq!((rv[0].wins, rv[0].votes), (0, s));
+            assert_eq!(rv.head_to_head(1, 0), (s, 0));
+            assert_eq!(rv.comparisons_done(), 1);
+        }
+    }
+
+    #[test]
+    fn rel_vec_vote_strength_clamped() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+
+        rv.vote_strength(0, 1, 0).unwrap();
+
+        assert_eq!((rv[0].wins, rv[0].votes, rv[1].votes), (1, 1, 1));
+
+        rv.vote_strength(1, 0, 200).unwrap();
+
+        assert_eq!((rv[1].wins, rv[1].votes, rv[0].votes), (3, 4, 4));
+    }
+
+    #[test]
+    fn pairwise_agreement_full() {
+        let mut a = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        let mut b = RelVec::from_strs(&["cde", "xyz", "bcd", "abc"]);
+        a.vote(0, 1).unwrap();
+        a.vote(1, 2).unwrap();
+        a.vote(0, 2).unwrap();
+        b.vote(3, 2).unwrap();
+        b.vote(2, 0).unwrap();
+        b.vote(1, 0).unwrap();
+
+        assert_eq!(pairwise_agreement(&a, &b), Some(1.0));
+
+        b.vote(0, 3).unwrap();
+
+        assert_eq!(pairwise_agreement(&a, &b), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn pairwise_agreement_disagreement() {
+        let mut a = RelVec::from_strs(&["abc", "bcd"]);
+        let mut b = RelVec::from_strs(&["abc", "bcd"]);
+
+        assert_eq!(pairwise_agreement(&a, &b), None);
+
+        a.vote(0, 1).unwrap();
+        b.vote(1, 0).unwrap();
+
+        assert_eq!(pairwise_agreement(&a, &b), Some(0.0));
+
+        let c = RelVec::from_strs(&["cde", "def"]);
+
+        assert_eq!(pairwise_agreement(&a, &c), None);
+    }
+
+    #[test]
+    fn rel_vec_clear() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.enable_vote_log();
+        rv.vote(0, 1).unwrap();
+        rv.skip(1, 2);
+
+        rv.clear();
+
+        assert!(rv.is_empty());
+        assert!(rv.capacity() >= 3);
+        assert_eq!(rv.comparisons_done(), 0);
+        assert_eq!(rv.skips(), 0);
+        assert!(rv.vote_log().is_empty());
+    }
+
+    #[test]
+    fn rel_vec_save_ranked_only() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 4),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 3, 4),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        rv.save_ranked_only("_rel_vec_save_ranked_only.txt")
+            .unwrap();
+
+        let b = RelVec::load("_rel_vec_save_ranked_only.txt").unwrap();
+
+        fs::remove_file("_rel_vec_save_ranked_only.txt").unwrap();
+
+        assert_eq!(
+            b.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["def", "bcd"].to_vec()
+        );
+        assert!(b.iter().all(|e| e.votes > 0));
+        assert_eq!(rv.len(), 4);
+        assert_eq!(rv[0].name, "abc");
+    }
+
+    #[test]
+    fn rel_vec_info_gain() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 5, 10),
+                RelEntry::new("bcd".to_owned(), 6, 10),
+                RelEntry::new("cde".to_owned(), 9, 10),
+                RelEntry::new("def".to_owned(), 0, 10),
+                RelEntry::new("efg".to_owned(), 0, 0),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert!(rv.info_gain(0, 1) > rv.info_gain(0, 2));
+        assert!(rv.info_gain(0, 2) > 0.0);
+        assert_eq!(rv.info_gain(0, 4), 1.0);
+        assert_eq!(rv.info_gain(2, 3), 0.0);
+        assert_eq!(rv.info_gain(0, 1), rv.info_gain(1, 0));
+    }
+
+    #[test]
+    fn rel_vec_undefeated_winless() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 3, 3),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry::new("cde".to_owned(), 0, 2),
+                RelEntry::new("def".to_owned(), 1, 2),
+                RelEntry::new("efg".to_owned(), 1, 1),
+                RelEntry::new("fgh".to_owned(), 0, 1),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+
+        assert_eq!(rv.undefeated(), [0, 4].to_vec());
+        assert_eq!(rv.winless(), [2, 5].to_vec());
+        assert!(RelVec::from_strs(&["abc", "bcd"]).undefeated().is_empty());
+        assert!(RelVec::from_strs(&["abc", "bcd"]).winless().is_empty());
+    }
+
+    #[test]
+    fn rel_vec_into_sorted_percentage() {
+        let rv = RelVec {
+            inner: [
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 4),
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 2, 4),
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
+        let mut sorted = rv.clone();
+        sorted.sort_percentage();
 
-        assert_eq!(rv.min_votes(), [0, 1].to_vec());
+        let v = rv.into_sorted_percentage();
+
+        assert_eq!(v, sorted.inner);
+        assert!(v.iter().zip(sorted.iter()).all(|(a, b)| a.deep_eq(b)));
     }
 
     #[test]
-    fn rel_vec_random_pair() {
-        for _ in 0..5 {
-            let mut rv = RelVec {
-                inner: [
-                    RelEntry {
-                        name: "abc".to_string(),
-                        wins: 0,
-                        votes: 0,
-                        locked: false,
-                    },
-                    RelEntry {
-                        name: "locked".to_string(),
-                        wins: 0,
-                        votes: 0,
-                        locked: true,
-                    },
-                    RelEntry {
-                        name: "def".to_string(),
-                        wins: 0,
-                        votes: 0,
-                        locked: false,
-                    },
-                ]
-                .to_vec(),
-                rng: rand::thread_rng(),
-            };
+    fn rel_vec_disable() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.vote(0, 2).unwrap();
 
-            let (a, b) = rv.random_pair().unwrap();
+        rv.disable("bcd").unwrap();
 
-            assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+        assert_eq!(rv.len(), 3);
+        assert_eq!(rv.reduced(), [0, 2].to_vec());
+        assert_eq!(rv.active_len(), 2);
+        assert!(!rv[1].locked);
+        for _ in 0..20 {
+            let (a, b) = rv.random_pair().unwrap();
+            assert!(a != 1 && b != 1);
+            let (a, b) = rv.min_pair().unwrap();
+            assert!(a != 1 && b != 1);
         }
+
+        rv.save("_rel_vec_disable.txt").unwrap();
+        let b = RelVec::load("_rel_vec_disable.txt").unwrap();
+        fs::remove_file("_rel_vec_disable.txt").unwrap();
+
+        assert!(b[1].disabled);
+        assert!(!b[0].disabled);
+
+        rv.enable("bcd").unwrap();
+
+        assert_eq!(rv.reduced(), [0, 1, 2].to_vec());
+        assert!(matches!(rv.disable("xyz"), Err(Error::NotFound(_))));
     }
 
     #[test]
-    fn rel_vec_min_pair() {
+    fn rel_vec_stats() {
         let mut rv = RelVec {
             inner: [
-                RelEntry {
-                    name: "abc".to_string(),
-                    wins: 0,
-                    votes: 2,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "locked".to_string(),
-                    wins: 0,
-                    votes: 0,
-                    locked: true,
-                },
-                RelEntry {
-                    name: "def".to_string(),
-                    wins: 0,
-                    votes: 1,
-                    locked: false,
-                },
+                RelEntry::new("abc".to_owned(), 3, 4),
+                RelEntry::new("bcd".to_owned(), 1, 4),
+                RelEntry::new("cde".to_owned(), 0, 0),
+                RelEntry::new("def".to_owned(), 2, 2),
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
+        rv[3].locked = true;
 
-        let (a, b) = rv.min_pair().unwrap();
+        assert_eq!(
+            rv.stats(),
+            RelStats {
+                entries: 4,
+                unlocked: 3,
+                total_votes: 10,
+                mean_percentage: Some(200.0 / 3.0),
+                coverage: 0.75,
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&RelVec::new().stats()).unwrap(),
+            "{\"entries\":0,\"unlocked\":0,\"total_votes\":0,\"mean_percentage\":null,\"coverage\":0.0}"
+        );
+    }
 
-        assert_eq!((a, b), (2, 0))
+    #[test]
+    fn rel_entry_compare_percentage_then_votes() {
+        let a = RelEntry::new("abc".to_owned(), 1, 2);
+        let b = RelEntry::new("bcd".to_owned(), 50, 100);
+        let c = RelEntry::new("cde".to_owned(), 2, 3);
+
+        assert_eq!(a.compare_percentage(&b), Ordering::Equal);
+        assert_eq!(a.compare_percentage_then_votes(&b), Ordering::Less);
+        assert_eq!(b.compare_percentage_then_votes(&a), Ordering::Greater);
+        assert_eq!(a.compare_percentage_then_votes(&a), Ordering::Equal);
+        assert_eq!(b.compare_percentage_then_votes(&c), Ordering::Less);
     }
 
     #[test]
-    fn rel_vec_equal_pair() {
+    fn rel_vec_sort_percentage_confident() {
         let mut rv = RelVec {
             inner: [
-                RelEntry {
-                    name: "abc".to_string(),
-                    wins: 1,
-                    votes: 2,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "locked".to_string(),
-                    wins: 1,
-                    votes: 1,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "def".to_string(),
-                    wins: 1,
-                    votes: 2,
-                    locked: false,
-                },
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 2),
+                RelEntry::new("cde".to_owned(), 1, 4),
+                RelEntry::new("def".to_owned(), 50, 100),
+                RelEntry::new("efg".to_owned(), 3, 3),
+                RelEntry::new("fgh".to_owned(), 0, 1),
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
 
-        let (a, b) = rv.equal_pair().unwrap();
+        rv.sort_percentage_confident();
 
-        assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+        assert_eq!(
+            rv.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(),
+            ["efg", "def", "bcd", "cde", "fgh", "abc"].to_vec()
+        );
     }
 
     #[test]
-    fn rel_vec_equal_pair_fail() {
+    fn rel_vec_search() {
+        let rv = RelVec::from_strs(&[
+            "The Matrix",
+            "Matrix Reloaded",
+            "Alien",
+            "Animatrix",
+            "matrix",
+        ]);
+
+        assert_eq!(rv.search("MATRIX"), [1, 4, 3, 0].to_vec());
+        assert_eq!(rv.search("ali"), [2].to_vec());
+        assert!(rv.search("xyz").is_empty());
+    }
+
+    #[test]
+    fn rel_vec_win_matrix() {
+        let mut rv = RelVec::from_strs(&["abc", "b,c", "cde"]);
+        rv.vote(0, 1).unwrap();
+        rv.vote(0, 1).unwrap();
+        rv.vote(1, 0).unwrap();
+        rv.vote(2, 0).unwrap();
+
+        assert_eq!(
+            rv.win_matrix(),
+            [[0, 2, 0].to_vec(), [1, 0, 0].to_vec(), [1, 0, 0].to_vec()].to_vec()
+        );
+
+        rv.save_matrix_csv("_rel_vec_win_matrix.csv").unwrap();
+
+        let content = fs::read_to_string("_rel_vec_win_matrix.csv").unwrap();
+
+        fs::remove_file("_rel_vec_win_matrix.csv").unwrap();
+
+        assert_eq!(
+            content,
+            ",abc,\"b,c\",cde\nabc,0,2,0\n\"b,c\",1,0,0\ncde,1,0,0\n"
+        );
+    }
+
+    #[test]
+    fn rel_entry_display_percentage() {
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 0, 0).display_percentage(),
+            "—"
+        );
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 0, 3).display_percentage(),
+            "0.0%"
+        );
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 2, 3).display_percentage(),
+            "66.7%"
+        );
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 0, 0).to_string(),
+            "abc - 0/0 - —"
+        );
+    }
+
+    #[test]
+    fn rel_entry_effective_votes() {
+        assert_eq!(RelEntry::new("abc".to_owned(), 0, 0).effective_votes(), 0);
+        assert_eq!(RelEntry::new("abc".to_owned(), 2, 7).effective_votes(), 7);
+
+        let mut rv = RelVec::from_strs(&["abc", "bcd"]);
+        rv.vote_weighted(0, 1, 3).unwrap();
+
+        assert_eq!(rv[0].effective_votes(), 3);
+        assert_eq!(rv[1].effective_votes(), 3);
+    }
+
+    #[test]
+    fn rel_vec_round_robin() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def", "efg"]);
+        rv.vote(0, 1).unwrap();
+
+        assert_eq!(rv.round_robin().len(), 5 * 4 / 2);
+        assert_eq!(rv.round_robin()[..2], [(0, 1), (0, 2)]);
+
+        rv[2].locked = true;
+
+        assert_eq!(
+            rv.round_robin(),
+            [(0, 1), (0, 3), (0, 4), (1, 3), (1, 4), (3, 4)].to_vec()
+        );
+        assert!(RelVec::from_strs(&["abc"]).round_robin().is_empty());
+    }
+
+    #[test]
+    fn rel_vec_equal_pair_first() {
         let mut rv = RelVec {
             inner: [
-                RelEntry {
-                    name: "abc".to_string(),
-                    wins: 1,
-                    votes: 2,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "locked".to_string(),
-                    wins: 1,
-                    votes: 1,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "def".to_string(),
-                    wins: 1,
-                    votes: 2,
-                    locked: true,
-                },
+                RelEntry::new("abc".to_owned(), 1, 3),
+                RelEntry::new("bcd".to_owned(), 0, 0),
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 0, 0),
+                RelEntry::new("efg".to_owned(), 2, 6),
+                RelEntry::new("fgh".to_owned(), 6, 8),
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
 
-        assert_eq!(rv.equal_pair(), None);
+        assert_eq!(rv.equal_pair_first(), Some((0, 4)));
+
+        rv[0].locked = true;
+
+        assert_eq!(rv.equal_pair_first(), Some((2, 5)));
+
+        rv[5].wins = 5;
+
+        assert_eq!(rv.equal_pair_first(), None);
     }
 
     #[test]
-    fn rel_vec_nearest_pair() {
-        let mut rv = RelVec {
+    fn rel_entry_percentage_clamped() {
+        let a = RelEntry::new("abc".to_owned(), 5, 4);
+
+        assert_eq!(a.percentage(), 125.0);
+        assert_eq!(a.percentage_clamped(), 100.0);
+        assert_eq!(a.display_percentage(), "100.0%");
+        assert_eq!(
+            RelEntry::new("abc".to_owned(), 1, 4).percentage_clamped(),
+            25.0
+        );
+        assert!(RelEntry::new("abc".to_owned(), 0, 0)
+            .percentage_clamped()
+            .is_nan());
+    }
+
+    #[test]
+    fn rel_vec_merge_with() {
+        let mut left = RelVec {
             inner: [
-                RelEntry {
-                    name: "abc".to_string(),
-                    wins: 1,
-                    votes: 2,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "locked".to_string(),
-                    wins: 1,
-                    votes: 1,
-                    locked: true,
-                },
-                RelEntry {
-                    name: "def".to_string(),
-                    wins: 5,
-                    votes: 8,
-                    locked: false,
-                },
+                RelEntry::new("abc".to_owned(), 1, 2),
+                RelEntry::new("bcd".to_owned(), 0, 1),
+            ]
+            .to_vec(),
+            ..RelVec::new()
+        };
+        left[0].beaten.insert("bcd".to_string(), 1);
+        let mut right = RelVec {
+            inner: [
+                RelEntry::new("cde".to_owned(), 2, 2),
+                RelEntry::new("abc".to_owned(), 3, 5),
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
+        right[1].locked = true;
+        right[1].beaten.insert("bcd".to_string(), 2);
 
-        let (a, b) = rv.nearest_pair().unwrap();
+        let stats = |rv: &RelVec| {
+            rv.iter()
+                .map(|e| (e.name.clone(), e.wins, e.votes, e.locked))
+                .collect::<Vec<(String, u32, u32, bool)>>()
+        };
+        let merged = |strategy| {
+            let mut rv = left.clone();
+            rv.merge_with(&right, strategy).unwrap();
+            rv
+        };
 
-        assert!((a, b) == (0, 2) || (a, b) == (2, 0));
+        let sum = merged(MergeStrategy::Sum);
+        assert_eq!(
+            stats(&sum),
+            [
+                ("abc".to_string(), 4, 7, true),
+                ("bcd".to_string(), 0, 1, false),
+                ("cde".to_string(), 2, 2, false)
+            ]
+            .to_vec()
+        );
+        assert_eq!(sum[0].wins_against("bcd"), 3);
+
+        assert_eq!(
+            stats(&merged(MergeStrategy::KeepLeft))[0],
+            ("abc".to_string(), 1, 2, false)
+        );
+        assert_eq!(
+            stats(&merged(MergeStrategy::KeepRight))[0],
+            ("abc".to_string(), 3, 5, true)
+        );
+        assert_eq!(
+            stats(&merged(MergeStrategy::Max))[0],
+            ("abc".to_string(), 3, 5, true)
+        );
+        assert_eq!(merged(MergeStrategy::KeepLeft).len(), 3);
+
+        right[1].votes = 2;
+        let mut tie = left.clone();
+        tie.merge_with(&right, MergeStrategy::Max).unwrap();
+
+        assert_eq!(stats(&tie)[0], ("abc".to_string(), 1, 2, false));
+
+        let mut full = RelVec {
+            inner: [RelEntry::new("abc".to_owned(), 0, u32::MAX)].to_vec(),
+            ..RelVec::new()
+        };
+        let before = full.clone();
+        let mut right = RelVec::from_strs(&["cde", "abc"]);
+        right[1].votes = 1;
+
+        assert!(matches!(
+            full.merge_with(&right, MergeStrategy::Sum),
+            Err(Error::Overflow(name)) if name == "abc"
+        ));
+        assert!(full.deep_eq(&before));
     }
 
     #[test]
-    fn rel_vec_nearest_pair_two() {
-        let mut rv = RelVec {
+    fn rel_vec_ranked_iter() {
+        let rv = RelVec {
             inner: [
-                RelEntry {
-                    name: "abc".to_string(),
-                    wins: 1,
-                    votes: 2,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "locked".to_string(),
-                    wins: 1,
-                    votes: 1,
-                    locked: false,
-                },
-                RelEntry {
-                    name: "def".to_string(),
-                    wins: 5,
-                    votes: 8,
-                    locked: true,
-                },
+                RelEntry::new("abc".to_owned(), 0, 0),
+                RelEntry::new("bcd".to_owned(), 1, 4),
+                RelEntry::new("cde".to_owned(), 3, 4),
+                RelEntry::new("def".to_owned(), 2, 8),
+                RelEntry::new("efg".to_owned(), 0, 0),
+                RelEntry::new("fgh".to_owned(), 4, 4),
             ]
             .to_vec(),
-            rng: rand::thread_rng(),
+            ..RelVec::new()
         };
-        let (a, b) = rv.nearest_pair().unwrap();
 
-        assert!((a, b) == (0, 1) || (a, b) == (1, 0));
+        let lazy: Vec<(usize, &RelEntry)> = rv.ranked_iter().collect();
+
+        assert_eq!(
+            lazy,
+            rv.ranked()
+                .into_iter()
+                .map(|(rank, e)| (rank - 1, e))
+                .collect::<Vec<(usize, &RelEntry)>>()
+        );
+        assert_eq!(
+            lazy.iter()
+                .map(|(rank, e)| (*rank, e.name.as_str()))
+                .collect::<Vec<(usize, &str)>>(),
+            [
+                (0, "fgh"),
+                (1, "cde"),
+                (2, "bcd"),
+                (2, "def"),
+                (4, "abc"),
+                (4, "efg")
+            ]
+            .to_vec()
+        );
+        assert_eq!(RelVec::new().ranked_iter().count(), 0);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn rel_vec_save_bin() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+        rv.seed(7);
+        rv.vote(0, 1).unwrap();
+        rv[1].locked = true;
+        rv[2].tags = ["movie".to_string()].to_vec();
+        rv[2].set_meta("url".to_string(), "x".to_string());
+        rv[2].weight = 2.0;
+        rv.random_pair();
+
+        rv.save_bin("_rel_vec_save_bin.bin").unwrap();
+
+        let mut b = RelVec::load_bin("_rel_vec_save_bin.bin").unwrap();
+
+        fs::remove_file("_rel_vec_save_bin.bin").unwrap();
+
+        assert!(rv.deep_eq(&b));
+        assert_eq!(b[0].beaten, rv[0].beaten);
+        assert_eq!(b[2].tags, rv[2].tags);
+        assert_eq!(b[2].get_meta("url"), Some("x"));
+        assert_eq!(b[2].weight, 2.0);
+        assert_eq!(b.random_pair(), rv.random_pair());
     }
 }
@@ -0,0 +1,82 @@
+use rand::{rngs::ThreadRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+/// Random number generator of a `RelVec`.
+///
+/// Either the thread local generator, or a seeded one whose position can be stored and
+/// restored.
+#[derive(Clone, Debug)]
+pub(crate) enum RelRng {
+    Thread(ThreadRng),
+    Seeded { seed: u64, rng: Box<ChaCha12Rng> },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RngState {
+    pub seed: u64,
+    /// Number of 32 bit words drawn since seeding.
+    pub draws: u64,
+}
+
+impl RelRng {
+    pub fn thread() -> Self {
+        RelRng::Thread(rand::thread_rng())
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        RelRng::Seeded {
+            seed,
+            rng: Box::new(ChaCha12Rng::seed_from_u64(seed)),
+        }
+    }
+
+    pub fn from_state(state: RngState) -> Self {
+        let mut rng = ChaCha12Rng::seed_from_u64(state.seed);
+        rng.set_word_pos(u128::from(state.draws));
+
+        RelRng::Seeded {
+            seed: state.seed,
+            rng: Box::new(rng),
+        }
+    }
+
+    pub fn state(&self) -> Option<RngState> {
+        match self {
+            RelRng::Thread(_) => None,
+            RelRng::Seeded { seed, rng } => Some(RngState {
+                seed: *seed,
+                draws: rng.get_word_pos() as u64,
+            }),
+        }
+    }
+}
+
+impl RngCore for RelRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            RelRng::Thread(rng) => rng.next_u32(),
+            RelRng::Seeded { rng, .. } => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            RelRng::Thread(rng) => rng.next_u64(),
+            RelRng::Seeded { rng, .. } => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            RelRng::Thread(rng) => rng.fill_bytes(dest),
+            RelRng::Seeded { rng, .. } => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            RelRng::Thread(rng) => rng.try_fill_bytes(dest),
+            RelRng::Seeded { rng, .. } => rng.try_fill_bytes(dest),
+        }
+    }
+}
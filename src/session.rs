@@ -0,0 +1,219 @@
+use crate::{
+    error::Error,
+    rel_vec::{Matchup, RelVec},
+    vote::VoteStrategy,
+};
+
+/// A voting session over a `RelVec`, yielding matchups and taking the votes for them.
+///
+/// An `Iterator` can't be used here as voting needs the `RelVec` while the iterator borrows
+/// it. A session owns that borrow instead, so a session loop looks like
+///
+/// ```
+/// # use relaty::{rel_vec::RelVec, vote::VoteStrategy};
+/// # let mut rv = RelVec::from_strs(&["a", "b", "c"]);
+/// # rv.session_limit = Some(3);
+/// let mut session = rv.session(VoteStrategy::Random);
+///
+/// while let Some(m) = session.next_matchup() {
+///     session.vote(m.left, m.right)?;
+/// }
+/// # Ok::<(), relaty::Error>(())
+/// ```
+///
+/// The loop ends when the strategy finds no pair, e.g. when the session limit is reached.
+pub struct Session<'a> {
+    rv: &'a mut RelVec,
+    strategy: VoteStrategy,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(rv: &'a mut RelVec, strategy: VoteStrategy) -> Self {
+        Session { rv, strategy }
+    }
+
+    pub fn next_matchup(&mut self) -> Option<Matchup> {
+        self.rv.next_matchup(&self.strategy)
+    }
+
+    pub fn vote(&mut self, winner: usize, loser: usize) -> Result<(), Error> {
+        self.rv.vote(winner, loser)
+    }
+
+    pub fn rel_vec(&self) -> &RelVec {
+        self.rv
+    }
+}
+
+/// A "best of" matchup between two entries, see `RelVec::best_of`.
+///
+/// The sub-results are only counted here. Once one side has `wins_needed` wins, a single vote
+/// for it is applied to the `RelVec`, so the main stats look like one normal comparison.
+pub struct BestOfSession<'a> {
+    rv: &'a mut RelVec,
+    a: usize,
+    b: usize,
+    wins_needed: u32,
+    wins: (u32, u32),
+}
+
+impl<'a> BestOfSession<'a> {
+    /// `wins_needed` of 0 is treated as 1. Fails with `Error::SameEntry` if `a == b`.
+    pub fn new(rv: &'a mut RelVec, a: usize, b: usize, wins_needed: u32) -> Result<Self, Error> {
+        if a == b {
+            return Err(Error::SameEntry(rv[a].name.clone()));
+        }
+
+        Ok(BestOfSession {
+            rv,
+            a,
+            b,
+            wins_needed: wins_needed.max(1),
+            wins: (0, 0),
+        })
+    }
+
+    /// Records that `winner`, which must be one of the two entries, won a single game.
+    ///
+    /// Applies the vote if this decides the matchup. Games after that are ignored. Fails
+    /// with `Error::NotInMatchup` if `winner` is not one of the two entries. If the vote
+    /// can't be applied, the deciding game is not counted either, so it can be recorded
+    /// again.
+    pub fn record(&mut self, winner: usize) -> Result<(), Error> {
+        if winner != self.a && winner != self.b {
+            return Err(Error::NotInMatchup(winner));
+        }
+
+        if self.is_decided() {
+            return Ok(());
+        }
+
+        let mut wins = self.wins;
+        if winner == self.a {
+            wins.0 += 1;
+        } else {
+            wins.1 += 1;
+        }
+
+        match self.winner_of(wins) {
+            Some(w) => {
+                let l = if w == self.a { self.b } else { self.a };
+                let before = self.rv.comparisons_done();
+                let result = self.rv.vote(w, l);
+
+                // A failed autosave still counts the vote
+                if self.rv.comparisons_done() != before {
+                    self.wins = wins;
+                }
+                result
+            }
+            None => {
+                self.wins = wins;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// The entry that reached `wins_needed` wins, if any.
+    pub fn winner(&self) -> Option<usize> {
+        self.winner_of(self.wins)
+    }
+
+    fn winner_of(&self, wins: (u32, u32)) -> Option<usize> {
+        if wins.0 >= self.wins_needed {
+            Some(self.a)
+        } else if wins.1 >= self.wins_needed {
+            Some(self.b)
+        } else {
+            None
+        }
+    }
+
+    /// Games won by the first and the second entry so far.
+    pub fn score(&self) -> (u32, u32) {
+        self.wins
+    }
+
+    pub fn rel_vec(&self) -> &RelVec {
+        self.rv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, rel_vec::RelVec, vote::VoteStrategy};
+
+    #[test]
+    fn session_full() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde", "def"]);
+        rv.session_limit = Some(6);
+
+        let mut session = rv.session(VoteStrategy::Random);
+        let mut rounds = 0;
+
+        while let Some(m) = session.next_matchup() {
+            assert_ne!(m.left, m.right);
+            session
+                .vote(m.left.min(m.right), m.left.max(m.right))
+                .unwrap();
+            rounds += 1;
+        }
+
+        assert_eq!(rounds, 6);
+        assert_eq!(session.rel_vec().comparisons_done(), 6);
+        assert_eq!(rv.iter().map(|e| e.votes).sum::<u32>(), 12);
+        assert_eq!(rv[3].wins, 0);
+        assert!(rv.session_complete());
+    }
+
+    #[test]
+    fn best_of_two_one() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+
+        let mut best_of = rv.best_of(0, 2, 2).unwrap();
+        best_of.record(2).unwrap();
+
+        assert!(!best_of.is_decided());
+        assert_eq!(best_of.rel_vec().comparisons_done(), 0);
+
+        best_of.record(0).unwrap();
+        best_of.record(2).unwrap();
+
+        assert!(best_of.is_decided());
+        assert_eq!(best_of.winner(), Some(2));
+        assert_eq!(best_of.score(), (1, 2));
+
+        best_of.record(0).unwrap();
+
+        assert_eq!(best_of.score(), (1, 2));
+        assert_eq!((rv[2].wins, rv[2].votes), (1, 1));
+        assert_eq!((rv[0].wins, rv[0].votes), (0, 1));
+        assert_eq!(rv.comparisons_done(), 1);
+    }
+
+    #[test]
+    fn best_of_invalid() {
+        let mut rv = RelVec::from_strs(&["abc", "bcd", "cde"]);
+
+        assert!(matches!(
+            rv.best_of(1, 1, 2),
+            Err(Error::SameEntry(name)) if name == "bcd"
+        ));
+
+        let mut best_of = rv.best_of(0, 1, 1).unwrap();
+
+        assert!(matches!(best_of.record(2), Err(Error::NotInMatchup(2))));
+        assert_eq!(best_of.score(), (0, 0));
+
+        rv[1].votes = u32::MAX;
+        let mut best_of = rv.best_of(0, 1, 1).unwrap();
+
+        assert!(matches!(best_of.record(0), Err(Error::Overflow(_))));
+        assert_eq!(best_of.score(), (0, 0));
+        assert!(!best_of.is_decided());
+    }
+}
@@ -1,43 +1,53 @@
 use std::{
     convert::TryFrom,
+    fmt,
     io::{self, Write},
 };
 
 use crate::{error::Error, rel_vec::RelVec};
 
+pub type ChooseFunction = Box<dyn FnMut(&mut RelVec) -> Option<(usize, usize)>>;
+
 pub enum VoteStrategy {
     Random,
     OneMin,
     Equal,
     MinEqual,
     Nearest,
+    UnvotedFirst,
 }
 
 impl VoteStrategy {
-    pub fn strategies() -> [&'static str; 5] {
-        ["random", "onemin", "equal", "minequal", "nearest"]
+    pub fn strategies() -> [&'static str; 6] {
+        [
+            "random", "onemin", "equal", "minequal", "nearest", "unvoted",
+        ]
     }
 
-    pub fn choose_function(&self) -> Box<dyn FnMut(&mut RelVec) -> Option<(usize, usize)>> {
+    pub fn choose_function(&self) -> ChooseFunction {
         match self {
             VoteStrategy::Random => Box::new(RelVec::random_pair),
             VoteStrategy::OneMin => Box::new(RelVec::min_pair),
             VoteStrategy::Equal => Box::new(RelVec::equal_pair),
             VoteStrategy::MinEqual => Box::new(RelVec::min_equal_pair),
             VoteStrategy::Nearest => Box::new(RelVec::nearest_pair),
+            VoteStrategy::UnvotedFirst => Box::new(RelVec::unvoted_pair),
         }
     }
 }
 
-impl ToString for VoteStrategy {
-    fn to_string(&self) -> String {
-        match self {
-            VoteStrategy::Random => "random".to_owned(),
-            VoteStrategy::OneMin => "onemin".to_owned(),
-            VoteStrategy::Equal => "equal".to_owned(),
-            VoteStrategy::MinEqual => "minequal".to_owned(),
-            VoteStrategy::Nearest => "nearest".to_owned(),
-        }
+impl fmt::Display for VoteStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VoteStrategy::Random => "random",
+            VoteStrategy::OneMin => "onemin",
+            VoteStrategy::Equal => "equal",
+            VoteStrategy::MinEqual => "minequal",
+            VoteStrategy::Nearest => "nearest",
+            VoteStrategy::UnvotedFirst => "unvoted",
+        };
+
+        write!(f, "{}", name)
     }
 }
 
@@ -51,12 +61,13 @@ impl TryFrom<&str> for VoteStrategy {
             "equal" => Ok(VoteStrategy::Equal),
             "minequal" => Ok(VoteStrategy::MinEqual),
             "nearest" => Ok(VoteStrategy::Nearest),
+            "unvoted" => Ok(VoteStrategy::UnvotedFirst),
             _ => Err(Error::ArgError),
         }
     }
 }
 
-pub(crate) fn vote<F: FnMut(&mut RelVec) -> Option<(usize, usize)> + Sized>(
+pub fn vote<F: FnMut(&mut RelVec) -> Option<(usize, usize)> + Sized>(
     input: &str,
     output: &str,
     rounds: u32,
@@ -67,7 +78,7 @@ pub(crate) fn vote<F: FnMut(&mut RelVec) -> Option<(usize, usize)> + Sized>(
     let reader = io::stdin();
 
     for _ in 0..rounds {
-        let (a, b) = match choose(&mut rv) {
+        let (a, b) = match rv.choose_pair(&mut choose) {
             Some((a, b)) => (a, b),
             None => {
                 println!("There is no matching pair.");
@@ -110,14 +121,11 @@ pub(crate) fn vote<F: FnMut(&mut RelVec) -> Option<(usize, usize)> + Sized>(
 
         if let Some(c) = cmd.chars().next() {
             if c == '1' {
-                rv[a].wins += 1;
-                rv[a].votes += 1;
-                rv[b].votes += 1;
+                rv.vote(a, b)?;
             } else if c == '2' {
-                rv[b].wins += 1;
-                rv[a].votes += 1;
-                rv[b].votes += 1;
+                rv.vote(b, a)?;
             } else if c == 'o' {
+                rv.skip(a, b);
             } else if c == 'x' {
                 (*rv).remove(a);
             } else if c == 'y' {
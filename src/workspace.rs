@@ -0,0 +1,145 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{error::Error, rel_vec::RelVec};
+
+/// Several named lists, e.g. one for movies and one for books.
+///
+/// `save_all` writes every list to its own `<name>.json` file in a directory and `load_all`
+/// reads them back, so list names must be valid file names.
+#[derive(Clone, Debug, Default)]
+pub struct Workspace {
+    lists: HashMap<String, RelVec>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `list` as `name`, returning the list previously stored under that name.
+    ///
+    /// Fails with `Error::InvalidListName` if `name` can't be used as a file name in
+    /// `save_all`: if it is empty, `.` or `..`, or contains a path separator.
+    pub fn add_list(&mut self, name: String, list: RelVec) -> Result<Option<RelVec>, Error> {
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(Error::InvalidListName(name));
+        }
+
+        Ok(self.lists.insert(name, list))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RelVec> {
+        self.lists.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut RelVec> {
+        self.lists.get_mut(name)
+    }
+
+    pub fn remove_list(&mut self, name: &str) -> Option<RelVec> {
+        self.lists.remove(name)
+    }
+
+    /// Names of all lists in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.lists.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lists.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lists.is_empty()
+    }
+
+    /// Saves every list with `RelVec::save` to `<name>.json` in `dir`, creating `dir` if
+    /// needed. Other files in `dir` are left alone.
+    pub fn save_all<P: AsRef<Path>>(&self, dir: P) -> Result<(), Error> {
+        let dir = dir.as_ref();
+
+        fs::create_dir_all(dir)?;
+        for (name, list) in &self.lists {
+            list.save(dir.join(format!("{}.json", name)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads every `.json` file in `dir` with `RelVec::load`, named after the file without
+    /// the extension. Other files and subdirectories are skipped.
+    pub fn load_all<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        let mut ws = Self::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().is_some_and(|e| e == "json") {
+                let name = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+
+                ws.add_list(name, RelVec::load(&path)?)?;
+            }
+        }
+
+        Ok(ws)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::Workspace;
+    use crate::{error::Error, rel_vec::RelVec};
+
+    #[test]
+    fn workspace_save_all() {
+        let mut ws = Workspace::new();
+        let mut movies = RelVec::from_strs(&["abc", "bcd"]);
+        movies.vote(1, 0).unwrap();
+        ws.add_list("movies".to_string(), movies).unwrap();
+        ws.add_list("books".to_string(), RelVec::from_strs(&["cde"]))
+            .unwrap();
+
+        ws.save_all("_workspace_save_all").unwrap();
+        fs::write("_workspace_save_all/notes.txt", "abc").unwrap();
+
+        let b = Workspace::load_all("_workspace_save_all").unwrap();
+
+        fs::remove_dir_all("_workspace_save_all").unwrap();
+
+        let mut names: Vec<&str> = b.names().collect();
+        names.sort_unstable();
+
+        assert_eq!(names, ["books", "movies"].to_vec());
+        assert!(b.get("movies").unwrap().deep_eq(ws.get("movies").unwrap()));
+        assert!(b.get("books").unwrap().deep_eq(ws.get("books").unwrap()));
+    }
+
+    #[test]
+    fn workspace_lists() {
+        let mut ws = Workspace::new();
+
+        assert!(ws
+            .add_list("movies".to_string(), RelVec::from_strs(&["abc"]))
+            .unwrap()
+            .is_none());
+        ws.get_mut("movies").unwrap().add("bcd".to_string());
+
+        assert_eq!(ws.get("movies").unwrap().len(), 2);
+        assert_eq!(ws.remove_list("movies").unwrap().len(), 2);
+        assert!(ws.get("movies").is_none());
+        assert!(ws.is_empty());
+
+        for name in ["", ".", "..", "../movies", "a/b", "a\\b"] {
+            assert!(matches!(
+                ws.add_list(name.to_string(), RelVec::new()),
+                Err(Error::InvalidListName(n)) if n == name
+            ));
+        }
+        assert!(ws.is_empty());
+    }
+}